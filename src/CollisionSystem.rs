@@ -1,10 +1,20 @@
+pub mod SeparatingAxis;
 pub mod Symulation;
+#[cfg(test)]
 mod Tests;
 
-use std::vec;
+use std::{
+    f32::consts::PI,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 use crate::MathUtilities::{Point, Position, Vector};
 
+use self::SeparatingAxis::{cross, ConvexCore};
+
+pub const MAX_POLYGON_VERTICES: usize = 8;
+const ROUND_OUTLINE_SEGMENTS: usize = 24;
+
 #[derive(Clone, Copy)]
 pub struct Circle {
     pub radius: f32,
@@ -12,7 +22,7 @@ pub struct Circle {
 
 impl Circle {
     pub fn new(radius: f32) -> Self {
-        Self { radius: radius }
+        Self { radius }
     }
 }
 
@@ -28,50 +38,536 @@ impl Rectangle {
     }
 }
 
+/// Rectangle turned by the rotation of its collider.
+#[derive(Clone, Copy)]
+pub struct OrientedRectangle {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl OrientedRectangle {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self { width, height }
+    }
+}
+
+/// Segment of `length` along the local x axis, with rounded ends of `radius`.
+#[derive(Clone, Copy)]
+pub struct Capsule {
+    pub length: f32,
+    pub radius: f32,
+}
+
+impl Capsule {
+    pub fn new(length: f32, radius: f32) -> Self {
+        Self { length, radius }
+    }
+}
+
+/// Convex polygon in local space, around the position of its collider. Kept in a fixed size
+/// array so that shapes stay `Copy`.
+#[derive(Clone, Copy)]
+pub struct ConvexPolygon {
+    vertices: [Point; MAX_POLYGON_VERTICES],
+    vertex_count: usize,
+}
+
+impl ConvexPolygon {
+    pub fn new(vertices: &[Point]) -> Self {
+        Self::try_new(vertices).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// The vertices may go around either way, but every corner has to turn the same way and
+    /// they have to go around only once.
+    pub fn try_new(vertices: &[Point]) -> Result<Self, String> {
+        if vertices.len() < 3 || vertices.len() > MAX_POLYGON_VERTICES {
+            return Err(format!(
+                "convex polygon needs from 3 to {} vertices, got {}",
+                MAX_POLYGON_VERTICES,
+                vertices.len()
+            ));
+        }
+
+        let mut turn_sign = 0.0;
+        let mut total_turn = 0.0;
+        for (index, vertex) in vertices.iter().enumerate() {
+            let previous = vertices[(index + vertices.len() - 1) % vertices.len()];
+            let next = vertices[(index + 1) % vertices.len()];
+            let incoming = *vertex - previous;
+            let outgoing = next - *vertex;
+
+            let turn = cross(incoming, outgoing);
+            if turn == 0.0 || turn.is_nan() {
+                return Err(format!(
+                    "convex polygon has a flat or repeated corner at vertex {}",
+                    index
+                ));
+            }
+            if turn * turn_sign < 0.0 {
+                return Err(format!("convex polygon isn't convex at vertex {}", index));
+            }
+            turn_sign = turn;
+            total_turn += f32::atan2(turn, incoming.dot(outgoing));
+        }
+
+        //a star turns the same way at every corner but goes around more than once
+        if (total_turn.abs() - 2.0 * PI).abs() > 0.01 {
+            return Err(String::from("convex polygon goes around more than once"));
+        }
+
+        let mut polygon = Self {
+            vertices: [Point::default(); MAX_POLYGON_VERTICES],
+            vertex_count: vertices.len(),
+        };
+        polygon.vertices[..vertices.len()].copy_from_slice(vertices);
+        Ok(polygon)
+    }
+
+    pub fn get_vertices(&self) -> &[Point] {
+        &self.vertices[..self.vertex_count]
+    }
+}
+
 #[derive(Clone, Copy)]
 pub enum CollisionShape {
+    /// Ignores the rotation of its collider.
     Circle(Circle),
+    /// Always axis aligned, ignores the rotation of its collider.
     Rectangle(Rectangle),
+    OrientedRectangle(OrientedRectangle),
+    Capsule(Capsule),
+    ConvexPolygon(ConvexPolygon),
 }
 
+/// Minimum translation data of a collision, seen from the first collider of the pair.
 #[derive(Clone, Copy)]
 pub struct CollisionInfo {
-    pub collision_depth: Vector,
+    /// Penetration direction, pointing from the first collider towards the second one.
+    pub normal: Vector,
+    pub depth: f32,
+    pub contact_point: Point,
+    /// Set when the hit was found by a swept test, as a fraction of this frame's motion.
+    pub time_of_impact: Option<f32>,
 }
 
 impl CollisionInfo {
-    pub fn new(collision_depth: Vector) -> Self {
-        Self { collision_depth }
+    pub fn new(normal: Vector, depth: f32, contact_point: Point) -> Self {
+        Self {
+            normal,
+            depth,
+            contact_point,
+            time_of_impact: None,
+        }
+    }
+
+    pub fn swept(normal: Vector, contact_point: Point, time_of_impact: f32) -> Self {
+        Self {
+            normal,
+            depth: 0.0,
+            contact_point,
+            time_of_impact: Some(time_of_impact),
+        }
+    }
+
+    /// Moving the first collider by minus this vector separates the pair.
+    pub fn get_collision_depth(&self) -> Vector {
+        self.normal * self.depth
     }
 
     pub fn symetrical(&self) -> Self {
         Self {
-            collision_depth: -self.collision_depth,
+            normal: -self.normal,
+            ..*self
         }
     }
 }
 
+#[derive(Clone, Copy)]
+pub struct SweepInfo {
+    /// Fraction of the motion, from 0 to 1, travelled before the first touch.
+    pub time_of_impact: f32,
+    /// Surface normal of the hit shape, pointing towards the moving one.
+    pub normal: Vector,
+}
+
 #[derive(Clone, Copy)]
 pub struct Collider {
     pub shape: CollisionShape,
     pub position: Position,
+    /// Displacement covered during the last update, ending at `position`. Circles with a motion
+    /// are swept so that fast objects can't pass through others between frames.
+    pub motion: Vector,
+    /// In degrees, the same way sfml shapes are rotated.
+    pub rotation: f32,
+    /// Share of the overlap each side of a solid contact is moved by, infinite for immovable.
+    pub mass: f32,
+    /// Solid contacts only push the side with the lower priority, masses matter on a tie.
+    pub pushback_priority: i32,
 }
 
 #[derive(Clone, Copy)]
+pub struct BoundingBox {
+    pub min: Position,
+    pub max: Position,
+}
+
+impl BoundingBox {
+    pub fn new(min: Position, max: Position) -> Self {
+        Self { min, max }
+    }
+
+    pub fn around(center: Position, half_size: Vector) -> Self {
+        Self::new(center - half_size, center + half_size)
+    }
+
+    pub fn contains(&self, other: &BoundingBox) -> bool {
+        self.min.get_x() <= other.min.get_x()
+            && self.min.get_y() <= other.min.get_y()
+            && self.max.get_x() >= other.max.get_x()
+            && self.max.get_y() >= other.max.get_y()
+    }
+
+    /// Distance along the ray at which it leaves the box, None when it never goes through it.
+    pub fn get_ray_exit_distance(&self, origin: Position, direction: Vector) -> Option<f32> {
+        let mut entry_distance = 0.0f32;
+        let mut exit_distance = f32::INFINITY;
+
+        let axes = [
+            (origin.get_x(), direction.get_x(), self.min.get_x(), self.max.get_x()),
+            (origin.get_y(), direction.get_y(), self.min.get_y(), self.max.get_y()),
+        ];
+        for (origin, direction, minimum, maximum) in axes {
+            if direction == 0.0 {
+                if origin < minimum || origin > maximum {
+                    return None;
+                }
+                continue;
+            }
+
+            let first = (minimum - origin) / direction;
+            let second = (maximum - origin) / direction;
+            entry_distance = f32::max(entry_distance, f32::min(first, second));
+            exit_distance = f32::min(exit_distance, f32::max(first, second));
+        }
+
+        (entry_distance <= exit_distance).then_some(exit_distance)
+    }
+
+    pub fn merged(&self, other: &BoundingBox) -> BoundingBox {
+        BoundingBox::new(
+            Vector::new(
+                f32::min(self.min.get_x(), other.min.get_x()),
+                f32::min(self.min.get_y(), other.min.get_y()),
+            ),
+            Vector::new(
+                f32::max(self.max.get_x(), other.max.get_x()),
+                f32::max(self.max.get_y(), other.max.get_y()),
+            ),
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum CollisionMask {
     Player,
     Weapon,
     Enemy,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CollisionResponse {
+    Solid,
+    Trigger,
+}
+
+/// Where a contact is in its life. `Exit` carries the info from the last frame of the contact.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContactPhase {
+    Enter,
+    Stay,
+    Exit,
+}
+
+static NEXT_ENTITY_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Identity of a collidable which, unlike its index in the symulation, stays the same between
+/// frames.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EntityId(u64);
+
+impl EntityId {
+    pub fn new() -> Self {
+        Self(NEXT_ENTITY_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
 pub trait Collidable {
+    fn get_id(&self) -> EntityId;
     fn get_collider(&self) -> Collider;
     fn get_mask(&self) -> CollisionMask;
-    fn react_to_collision(&mut self, info: CollisionInfo, other_mask: CollisionMask);
+    /// Called by the solver to move the collidable out of solid contacts.
+    fn translate(&mut self, offset: Vector);
+    fn react_to_collision(
+        &mut self,
+        info: CollisionInfo,
+        other_mask: CollisionMask,
+        response: CollisionResponse,
+        phase: ContactPhase,
+    );
 }
 
 impl Collider {
+    pub const MIN_MASS: f32 = 0.001;
+
+    pub fn new(shape: CollisionShape, position: Position) -> Self {
+        Self {
+            shape,
+            position,
+            motion: Vector::default(),
+            rotation: 0.0,
+            mass: 1.0,
+            pushback_priority: 0,
+        }
+    }
+
+    pub fn with_motion(mut self, motion: Vector) -> Self {
+        self.motion = motion;
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Without a positive mass the solver would move the collider to infinity, so it is
+    /// clamped to `MIN_MASS`.
+    pub fn with_mass(mut self, mass: f32) -> Self {
+        self.mass = f32::max(mass, Self::MIN_MASS);
+        self
+    }
+
+    pub fn with_pushback_priority(mut self, pushback_priority: i32) -> Self {
+        self.pushback_priority = pushback_priority;
+        self
+    }
+
     pub fn collide(first: Self, second: Self) -> Option<CollisionInfo> {
+        let overlap = Self::overlap(first, second);
+        if overlap.is_some() {
+            return overlap;
+        }
+
+        Self::swept_collide(first, second)
+    }
+
+    fn swept_collide(first: Self, second: Self) -> Option<CollisionInfo> {
+        let relative_motion = first.motion - second.motion;
+        if relative_motion.length() <= 0.0 {
+            return None;
+        }
+
+        match (first.shape, second.shape) {
+            (CollisionShape::Circle(circle), _) => {
+                let start = first.position - relative_motion;
+                Self::swept_circle_collision(start, circle.radius, relative_motion, second).map(
+                    |sweep_info| {
+                        let contact_point = start
+                            + relative_motion * sweep_info.time_of_impact
+                            - sweep_info.normal * circle.radius;
+                        CollisionInfo::swept(
+                            -sweep_info.normal,
+                            contact_point,
+                            sweep_info.time_of_impact,
+                        )
+                    },
+                )
+            }
+            (_, CollisionShape::Circle(circle)) => {
+                let start = second.position + relative_motion;
+                Self::swept_circle_collision(start, circle.radius, -relative_motion, first).map(
+                    |sweep_info| {
+                        let contact_point = start
+                            - relative_motion * sweep_info.time_of_impact
+                            - sweep_info.normal * circle.radius;
+                        CollisionInfo::swept(
+                            sweep_info.normal,
+                            contact_point,
+                            sweep_info.time_of_impact,
+                        )
+                    },
+                )
+            }
+            _ => None,
+        }
+    }
+
+    /// Moves a circle from `start` by `motion` and returns the first touch with `other`,
+    /// which is treated as static.
+    pub fn swept_circle_collision(
+        start: Position,
+        radius: f32,
+        motion: Vector,
+        other: Collider,
+    ) -> Option<SweepInfo> {
+        match other.shape {
+            CollisionShape::Circle(circle) => {
+                Self::ray_to_circle(start, motion, other.position, radius + circle.radius)
+            }
+            CollisionShape::Rectangle(rectangle) => {
+                Self::swept_circle_to_rectangle(start, radius, motion, other.position, rectangle)
+            }
+            //the circle against any other shape is a point against the shape grown by its radius
+            _ => {
+                let mut core = ConvexCore::from_collider(&other);
+                core.radius += radius;
+                core.cast_ray(start, motion)
+                    .map(|(time_of_impact, normal)| SweepInfo {
+                        time_of_impact,
+                        normal,
+                    })
+            }
+        }
+    }
+
+    fn ray_to_circle(
+        origin: Position,
+        motion: Vector,
+        circle_position: Position,
+        radius: f32,
+    ) -> Option<SweepInfo> {
+        let offset = origin - circle_position;
+
+        if offset.length() < radius {
+            let normal = offset.normal().unwrap_or(Vector::new(1.0, 0.0));
+            return Some(SweepInfo {
+                time_of_impact: 0.0,
+                normal,
+            });
+        }
+
+        let a = motion.dot(motion);
+        let b = 2.0 * offset.dot(motion);
+        let c = offset.dot(offset) - radius * radius;
+
+        let discriminant = b * b - 4.0 * a * c;
+        if a <= 0.0 || discriminant < 0.0 {
+            return None;
+        }
+
+        let time_of_impact = (-b - discriminant.sqrt()) / (2.0 * a);
+        if !(0.0..=1.0).contains(&time_of_impact) {
+            return None;
+        }
+
+        let hit_position = origin + motion * time_of_impact;
+        let normal = (hit_position - circle_position)
+            .normal()
+            .unwrap_or_default();
+
+        Some(SweepInfo {
+            time_of_impact,
+            normal,
+        })
+    }
+
+    fn swept_circle_to_rectangle(
+        start: Position,
+        radius: f32,
+        motion: Vector,
+        rectangle_position: Position,
+        rectangle: Rectangle,
+    ) -> Option<SweepInfo> {
+        let half_size = Vector::new(rectangle.width, rectangle.height) * 0.5;
+        let expanded_half_size = half_size + Vector::new(radius, radius);
+
+        let (time_of_impact, normal) =
+            Self::ray_to_box(start, motion, rectangle_position, expanded_half_size)?;
+
+        //the expanded box has sharp corners while the real swept shape has rounded ones
+        let hit_position = start + motion * time_of_impact;
+        let local_hit = hit_position - rectangle_position;
+        let is_in_corner_region = local_hit.get_x().abs() > half_size.get_x()
+            && local_hit.get_y().abs() > half_size.get_y();
+
+        if !is_in_corner_region {
+            return Some(SweepInfo {
+                time_of_impact,
+                normal,
+            });
+        }
+
+        let corner = rectangle_position
+            + Vector::new(
+                half_size.get_x() * local_hit.get_x().signum(),
+                half_size.get_y() * local_hit.get_y().signum(),
+            );
+
+        Self::ray_to_circle(start, motion, corner, radius)
+    }
+
+    fn ray_to_box(
+        origin: Position,
+        motion: Vector,
+        box_position: Position,
+        half_size: Vector,
+    ) -> Option<(f32, Vector)> {
+        let minimum = box_position - half_size;
+        let maximum = box_position + half_size;
+
+        let mut time_of_entry = 0.0f32;
+        let mut time_of_leave = 1.0f32;
+        let mut normal = Vector::default();
+
+        let axes = [
+            (
+                origin.get_x(),
+                motion.get_x(),
+                minimum.get_x(),
+                maximum.get_x(),
+                Vector::new(1.0, 0.0),
+            ),
+            (
+                origin.get_y(),
+                motion.get_y(),
+                minimum.get_y(),
+                maximum.get_y(),
+                Vector::new(0.0, 1.0),
+            ),
+        ];
+
+        for (origin, motion, minimum, maximum, axis) in axes {
+            if motion == 0.0 {
+                if origin < minimum || origin > maximum {
+                    return None;
+                }
+                continue;
+            }
+
+            let mut near = (minimum - origin) / motion;
+            let mut far = (maximum - origin) / motion;
+            let mut near_normal = -axis;
+
+            if near > far {
+                std::mem::swap(&mut near, &mut far);
+                near_normal = axis;
+            }
+
+            if near > time_of_entry {
+                time_of_entry = near;
+                normal = near_normal;
+            }
+            time_of_leave = f32::min(time_of_leave, far);
+
+            if time_of_entry > time_of_leave {
+                return None;
+            }
+        }
+
+        Some((time_of_entry, normal))
+    }
+
+    fn overlap(first: Self, second: Self) -> Option<CollisionInfo> {
         match (first.shape, second.shape) {
             (CollisionShape::Circle(first_circle), CollisionShape::Circle(second_circle)) => {
                 Self::circle_to_circle_collision(
@@ -96,6 +592,7 @@ impl Collider {
                     first.position,
                     rectangle,
                 )
+                .map(|info| info.symetrical())
             }
             (
                 CollisionShape::Rectangle(first_rectangle),
@@ -106,6 +603,10 @@ impl Collider {
                 second.position,
                 second_rectangle,
             ),
+            _ => SeparatingAxis::collide(
+                ConvexCore::from_collider(&first),
+                ConvexCore::from_collider(&second),
+            ),
         }
     }
 
@@ -124,12 +625,14 @@ impl Collider {
             return None;
         }
 
-        let direction = (second_position - first_position)
+        //concentric circles have no preferred direction, any axis separates them
+        let normal = (second_position - first_position)
             .normal()
-            .unwrap_or_default();
-        let depth = (second_position - first_position).length();
+            .unwrap_or(Vector::new(1.0, 0.0));
+        let depth = sum_of_radii - distance_between_circles;
+        let contact_point = first_position + normal * (first_radius - depth * 0.5);
 
-        Some(CollisionInfo::new(direction * depth))
+        Some(CollisionInfo::new(normal, depth, contact_point))
     }
 
     fn circle_to_rectangle_collision(
@@ -138,38 +641,84 @@ impl Collider {
         rectangle_position: Position,
         rectangle: Rectangle,
     ) -> Option<CollisionInfo> {
-        let rectangle_offset_position =
-            rectangle_position - Vector::new(rectangle.width, rectangle.height) * 0.5;
+        let half_size = Vector::new(rectangle.width, rectangle.height) * 0.5;
+        let minimum = rectangle_position - half_size;
+        let maximum = rectangle_position + half_size;
 
         let nearest_x = f32::max(
-            rectangle_offset_position.get_x(),
-            f32::min(
-                circle_position.get_x(),
-                rectangle_offset_position.get_x() + rectangle.width,
-            ),
+            minimum.get_x(),
+            f32::min(circle_position.get_x(), maximum.get_x()),
         );
         let nearest_y = f32::max(
-            rectangle_offset_position.get_y(),
-            f32::min(
-                circle_position.get_y(),
-                rectangle_offset_position.get_y() + rectangle.height,
-            ),
+            minimum.get_y(),
+            f32::min(circle_position.get_y(), maximum.get_y()),
         );
 
         let nearest_on_rectangle = Vector::new(nearest_x, nearest_y);
-
         let difference = nearest_on_rectangle - circle_position;
 
-        let are_colliding = difference.length() < circle_radius;
-
-        if !are_colliding{
-            return None
+        match difference.normal() {
+            Some(normal) => {
+                let distance = difference.length();
+                if distance >= circle_radius {
+                    return None;
+                }
+
+                Some(CollisionInfo::new(
+                    normal,
+                    circle_radius - distance,
+                    nearest_on_rectangle,
+                ))
+            }
+            None => Self::circle_inside_rectangle_collision(
+                circle_position,
+                circle_radius,
+                minimum,
+                maximum,
+            ),
         }
+    }
 
-        let direction = (nearest_on_rectangle - circle_position).normal().unwrap_or_default();
-        let point_on_circle = circle_position + direction * circle_radius;
-
-        Some(CollisionInfo::new((nearest_on_rectangle - point_on_circle)))
+    fn circle_inside_rectangle_collision(
+        circle_position: Position,
+        circle_radius: f32,
+        minimum: Position,
+        maximum: Position,
+    ) -> Option<CollisionInfo> {
+        //the circle has to leave through the nearest edge, so it is pushed away from it
+        let edges = [
+            (
+                circle_position.get_x() - minimum.get_x(),
+                Vector::new(1.0, 0.0),
+                Vector::new(minimum.get_x(), circle_position.get_y()),
+            ),
+            (
+                maximum.get_x() - circle_position.get_x(),
+                Vector::new(-1.0, 0.0),
+                Vector::new(maximum.get_x(), circle_position.get_y()),
+            ),
+            (
+                circle_position.get_y() - minimum.get_y(),
+                Vector::new(0.0, 1.0),
+                Vector::new(circle_position.get_x(), minimum.get_y()),
+            ),
+            (
+                maximum.get_y() - circle_position.get_y(),
+                Vector::new(0.0, -1.0),
+                Vector::new(circle_position.get_x(), maximum.get_y()),
+            ),
+        ];
+
+        let (distance_to_edge, normal, contact_point) = edges
+            .into_iter()
+            .min_by(|first, second| first.0.total_cmp(&second.0))
+            .unwrap();
+
+        Some(CollisionInfo::new(
+            normal,
+            distance_to_edge + circle_radius,
+            contact_point,
+        ))
     }
 
     fn rectangle_to_rectangle_collision(
@@ -178,60 +727,109 @@ impl Collider {
         second_position: Position,
         second: Rectangle,
     ) -> Option<CollisionInfo> {
-        let first_offset_position = first_position - Vector::new(first.width, first.height) * 0.5;
-        let second_offset_position =
-            second_position - Vector::new(second.width, second.height) * 0.5;
+        let vector_between_centers = second_position - first_position;
+
+        let overlap_x = (first.width + second.width) * 0.5 - vector_between_centers.get_x().abs();
+        let overlap_y = (first.height + second.height) * 0.5 - vector_between_centers.get_y().abs();
 
-        let are_colliding = first_offset_position.get_x() < second_offset_position.get_x() + second.width
-            && first_offset_position.get_x() + first.width > second_offset_position.get_x()
-            && first_offset_position.get_y() < second_offset_position.get_y() + second.height
-            && first_offset_position.get_y() + first.height > second_offset_position.get_y();
+        let are_colliding = overlap_x > 0.0 && overlap_y > 0.0;
 
-        if !are_colliding{
-            return None
+        if !are_colliding {
+            return None;
         }
-         
-        let vector_between_centers = second_offset_position - first_offset_position;
-        let normal = vector_between_centers.normal().unwrap_or_default();
 
-        let first_support_point = first_position + Vector::new(normal.get_x().signum() * (first.width / 2.0), normal.get_y().signum() * (first.height/2.0));
-        let second_support_point = second_position + Vector::new(-normal.get_x().signum() * (second.width / 2.0), -normal.get_y().signum() * (second.height/2.0));
+        let direction_x = if vector_between_centers.get_x() < 0.0 { -1.0 } else { 1.0 };
+        let direction_y = if vector_between_centers.get_y() < 0.0 { -1.0 } else { 1.0 };
+
+        let (normal, depth) = if overlap_x < overlap_y {
+            (Vector::new(direction_x, 0.0), overlap_x)
+        } else {
+            (Vector::new(0.0, direction_y), overlap_y)
+        };
+
+        let first_half_size = Vector::new(first.width, first.height) * 0.5;
+        let second_half_size = Vector::new(second.width, second.height) * 0.5;
+        let first_minimum = first_position - first_half_size;
+        let first_maximum = first_position + first_half_size;
+        let second_minimum = second_position - second_half_size;
+        let second_maximum = second_position + second_half_size;
+
+        //middle of the overlapping area
+        let contact_point = Vector::new(
+            (f32::max(first_minimum.get_x(), second_minimum.get_x())
+                + f32::min(first_maximum.get_x(), second_maximum.get_x()))
+                * 0.5,
+            (f32::max(first_minimum.get_y(), second_minimum.get_y())
+                + f32::min(first_maximum.get_y(), second_maximum.get_y()))
+                * 0.5,
+        );
 
-        let mut depth_vector = first_support_point - second_support_point;
-        
-        if depth_vector.get_x().abs() > depth_vector.get_y().abs(){
-            depth_vector = Vector::new(0.0, depth_vector.get_y());
-        }
-        else{
-            depth_vector = Vector::new(depth_vector.get_x(), 0.0);
-        }
+        Some(CollisionInfo::new(normal, depth, contact_point))
+    }
 
-        let depth = depth_vector * 0.5;
+    pub fn bounding_box(&self) -> BoundingBox {
+        let core = ConvexCore::from_collider(self);
+        let radius = Vector::new(core.radius, core.radius);
+
+        let mut minimum = self.position;
+        let mut maximum = self.position;
+        for vertex in core.get_vertices() {
+            minimum = Vector::new(
+                f32::min(minimum.get_x(), vertex.get_x()),
+                f32::min(minimum.get_y(), vertex.get_y()),
+            );
+            maximum = Vector::new(
+                f32::max(maximum.get_x(), vertex.get_x()),
+                f32::max(maximum.get_y(), vertex.get_y()),
+            );
+        }
 
-        Some(CollisionInfo::new(depth))
+        //covers the whole way travelled during the last update
+        let start_offset = -self.motion;
+        let minimum = minimum
+            + Vector::new(
+                f32::min(0.0, start_offset.get_x()),
+                f32::min(0.0, start_offset.get_y()),
+            );
+        let maximum = maximum
+            + Vector::new(
+                f32::max(0.0, start_offset.get_x()),
+                f32::max(0.0, start_offset.get_y()),
+            );
+
+        BoundingBox::new(minimum - radius, maximum + radius)
     }
 
-    fn is_point_in_rectangle(
-        rectangle_position: Position,
-        rectangle: Rectangle,
-        point: Point,
-    ) -> bool {
-        let rectangle_offset_position =
-            rectangle_position - Vector::new(rectangle.width, rectangle.height) * 0.5;
+    /// Points around the collider, with its round parts split into segments.
+    pub fn get_outline(&self) -> Vec<Point> {
+        let core = ConvexCore::from_collider(self);
+        let vertices = core.get_vertices();
 
-        let is_in_x = point.get_x() > rectangle_offset_position.get_x()
-            && point.get_x() < rectangle_offset_position.get_x() + rectangle.width;
-        let is_in_y = point.get_y() > rectangle_offset_position.get_y()
-            && point.get_y() < rectangle_offset_position.get_y() + rectangle.height;
+        if core.radius <= 0.0 {
+            return vertices.to_vec();
+        }
 
-        let result = is_in_x && is_in_y;
+        match vertices {
+            [center] => Self::arc(*center, core.radius, 0.0, 2.0 * PI),
+            [start, end, ..] => {
+                //half circle around each end of the segment, joined by the straight sides
+                let direction = *end - *start;
+                let angle = f32::atan2(direction.get_y(), direction.get_x());
 
-        result
+                let mut outline = Self::arc(*end, core.radius, angle - PI * 0.5, PI);
+                outline.extend(Self::arc(*start, core.radius, angle + PI * 0.5, PI));
+                outline
+            }
+            [] => vec![],
+        }
     }
 
-    pub fn outer_rectangle_circle(rectangle: Rectangle) -> Circle {
-        let radius =
-            f32::sqrt(f32::powi(rectangle.height, 2) + f32::powi(rectangle.width, 2)) / 2.0;
-        Circle::new(radius)
+    fn arc(center: Point, radius: f32, start_angle: f32, angle: f32) -> Vec<Point> {
+        (0..=ROUND_OUTLINE_SEGMENTS)
+            .map(|i| {
+                let point_angle = start_angle + angle * i as f32 / ROUND_OUTLINE_SEGMENTS as f32;
+                center + Vector::new(point_angle.cos(), point_angle.sin()) * radius
+            })
+            .collect()
     }
 }
@@ -0,0 +1,374 @@
+use crate::MathUtilities::{Point, Position, Vector};
+
+use super::{Collider, CollisionInfo, CollisionShape, MAX_POLYGON_VERTICES};
+
+/// Every shape seen as a convex core (a point, a segment or a polygon) inflated by a radius.
+/// Circles are points with a radius, capsules are segments with a radius.
+#[derive(Clone, Copy)]
+pub struct ConvexCore {
+    vertices: [Point; MAX_POLYGON_VERTICES],
+    vertex_count: usize,
+    pub radius: f32,
+}
+
+impl ConvexCore {
+    pub fn from_collider(collider: &Collider) -> Self {
+        let position = collider.position;
+        let rotation = collider.rotation;
+
+        match collider.shape {
+            CollisionShape::Circle(circle) => Self::new(&[position], circle.radius),
+            CollisionShape::Rectangle(rectangle) => {
+                let half_size = Vector::new(rectangle.width, rectangle.height) * 0.5;
+                Self::new(&Self::box_corners(position, half_size, 0.0), 0.0)
+            }
+            CollisionShape::OrientedRectangle(rectangle) => {
+                let half_size = Vector::new(rectangle.width, rectangle.height) * 0.5;
+                Self::new(&Self::box_corners(position, half_size, rotation), 0.0)
+            }
+            CollisionShape::Capsule(capsule) => {
+                let half_segment = Vector::new(capsule.length * 0.5, 0.0).rotated(rotation);
+                Self::new(
+                    &[position - half_segment, position + half_segment],
+                    capsule.radius,
+                )
+            }
+            CollisionShape::ConvexPolygon(polygon) => {
+                let mut vertices = [Point::default(); MAX_POLYGON_VERTICES];
+                for (i, vertex) in polygon.get_vertices().iter().enumerate() {
+                    vertices[i] = position + vertex.rotated(rotation);
+                }
+                Self::new(&vertices[..polygon.get_vertices().len()], 0.0)
+            }
+        }
+    }
+
+    fn new(vertices: &[Point], radius: f32) -> Self {
+        let mut core = Self {
+            vertices: [Point::default(); MAX_POLYGON_VERTICES],
+            vertex_count: vertices.len(),
+            radius,
+        };
+        core.vertices[..vertices.len()].copy_from_slice(vertices);
+        core
+    }
+
+    fn box_corners(position: Position, half_size: Vector, rotation: f32) -> [Point; 4] {
+        [
+            Vector::new(-half_size.get_x(), -half_size.get_y()),
+            Vector::new(half_size.get_x(), -half_size.get_y()),
+            Vector::new(half_size.get_x(), half_size.get_y()),
+            Vector::new(-half_size.get_x(), half_size.get_y()),
+        ]
+        .map(|corner| position + corner.rotated(rotation))
+    }
+
+    pub fn get_vertices(&self) -> &[Point] {
+        &self.vertices[..self.vertex_count]
+    }
+
+    pub fn center(&self) -> Point {
+        let mut sum = Vector::default();
+        for vertex in self.get_vertices() {
+            sum += *vertex;
+        }
+        sum * (1.0 / self.vertex_count as f32)
+    }
+
+    fn edges(&self) -> Vec<(Point, Point)> {
+        let vertices = self.get_vertices();
+        match vertices.len() {
+            1 => vec![(vertices[0], vertices[0])],
+            2 => vec![(vertices[0], vertices[1])],
+            count => (0..count)
+                .map(|i| (vertices[i], vertices[(i + 1) % count]))
+                .collect(),
+        }
+    }
+
+    /// Edge normals, plus the extra axes a point or a segment needs for the test to stay exact.
+    fn separating_axes(&self) -> Vec<Vector> {
+        let mut axes: Vec<Vector> = self
+            .edges()
+            .iter()
+            .filter_map(|(start, end)| (*end - *start).perpendicular().normal())
+            .collect();
+
+        match self.vertex_count {
+            1 => {
+                axes.push(Vector::new(1.0, 0.0));
+                axes.push(Vector::new(0.0, 1.0));
+            }
+            2 => {
+                if let Some(direction) = (self.vertices[1] - self.vertices[0]).normal() {
+                    axes.push(direction);
+                }
+            }
+            _ => {}
+        }
+
+        axes
+    }
+
+    fn project(&self, axis: Vector) -> (f32, f32) {
+        self.get_vertices()
+            .iter()
+            .map(|vertex| vertex.dot(axis))
+            .fold((f32::MAX, f32::MIN), |(minimum, maximum), projection| {
+                (f32::min(minimum, projection), f32::max(maximum, projection))
+            })
+    }
+
+    /// First touch of a point moving from `origin` by `motion`, as the fraction of the motion
+    /// travelled and the surface normal there. The rounded parts are circles around the
+    /// vertices and the straight parts are the edges moved out by the radius.
+    pub fn cast_ray(&self, origin: Point, motion: Vector) -> Option<(f32, Vector)> {
+        let offset = origin - self.closest_point(origin);
+        if offset.length() <= self.radius {
+            let normal = offset
+                .normal()
+                .or((origin - self.center()).normal())
+                .unwrap_or(Vector::new(1.0, 0.0));
+            return Some((0.0, normal));
+        }
+
+        let mut hits: Vec<(f32, Vector)> = vec![];
+        if self.radius > 0.0 {
+            for vertex in self.get_vertices() {
+                if let Some(time) = ray_to_circle(origin, motion, *vertex, self.radius) {
+                    let normal = (origin + motion * time - *vertex).normal().unwrap_or_default();
+                    hits.push((time, normal));
+                }
+            }
+        }
+        for (start, end) in self.edges() {
+            let normal = match (end - start).perpendicular().normal() {
+                Some(normal) => normal,
+                None => continue,
+            };
+            for side in [normal, -normal] {
+                //only the side facing the motion can be entered
+                if motion.dot(side) >= 0.0 {
+                    continue;
+                }
+                let offset = side * self.radius;
+                if let Some(time) = ray_to_segment(origin, motion, start + offset, end + offset) {
+                    hits.push((time, side));
+                }
+            }
+        }
+
+        hits.into_iter()
+            .filter(|(time, _)| (0.0..=1.0).contains(time))
+            .min_by(|first, second| first.0.total_cmp(&second.0))
+    }
+
+    fn closest_point(&self, point: Point) -> Point {
+        if self.vertex_count >= 3 && self.contains(point) {
+            return point;
+        }
+
+        self.edges()
+            .into_iter()
+            .map(|(start, end)| closest_point_on_segment(point, start, end))
+            .min_by(|first, second| {
+                (*first - point)
+                    .length()
+                    .total_cmp(&(*second - point).length())
+            })
+            .unwrap_or(point)
+    }
+
+    //the polygon may go around either way, the point is inside when it is on the same side of
+    //every edge
+    fn contains(&self, point: Point) -> bool {
+        let mut side = 0.0;
+        for (start, end) in self.edges() {
+            let cross = cross(end - start, point - start);
+            if cross * side < 0.0 {
+                return false;
+            }
+            if cross != 0.0 {
+                side = cross;
+            }
+        }
+        true
+    }
+
+    /// Furthest point in the direction, the middle of the edge when an edge faces it.
+    fn support_point(&self, direction: Vector) -> Point {
+        let (_, maximum) = self.project(direction);
+        let tolerance = 0.0001 * f32::max(1.0, maximum.abs());
+
+        let mut sum = Vector::default();
+        let mut count = 0;
+        for vertex in self.get_vertices() {
+            if maximum - vertex.dot(direction) <= tolerance {
+                sum += *vertex;
+                count += 1;
+            }
+        }
+
+        sum * (1.0 / count as f32)
+    }
+}
+
+pub fn collide(first: ConvexCore, second: ConvexCore) -> Option<CollisionInfo> {
+    match minimum_overlap(&first, &second) {
+        Some((axis, overlap)) => overlapping_cores_collision(&first, &second, axis, overlap),
+        None => separated_cores_collision(&first, &second),
+    }
+}
+
+/// Axis of the smallest core overlap, pointing from the first core to the second, or `None`
+/// when some axis separates the cores.
+fn minimum_overlap(first: &ConvexCore, second: &ConvexCore) -> Option<(Vector, f32)> {
+    let mut axes = first.separating_axes();
+    axes.extend(second.separating_axes());
+
+    let mut minimum: Option<(Vector, f32)> = None;
+
+    for axis in axes {
+        let (first_min, first_max) = first.project(axis);
+        let (second_min, second_max) = second.project(axis);
+
+        //distance needed to push the second core out on either side, which is more than the
+        //shared length when one of the projections holds the other
+        let forward_overlap = first_max - second_min;
+        let backward_overlap = second_max - first_min;
+        if forward_overlap < 0.0 || backward_overlap < 0.0 {
+            return None;
+        }
+
+        let (axis, overlap) = if forward_overlap < backward_overlap {
+            (axis, forward_overlap)
+        } else if backward_overlap < forward_overlap {
+            (-axis, backward_overlap)
+        } else if (second.center() - first.center()).dot(axis) < 0.0 {
+            (-axis, forward_overlap)
+        } else {
+            (axis, forward_overlap)
+        };
+
+        if minimum.is_none_or(|(_, minimum_overlap)| overlap < minimum_overlap) {
+            minimum = Some((axis, overlap));
+        }
+    }
+
+    minimum
+}
+
+fn overlapping_cores_collision(
+    first: &ConvexCore,
+    second: &ConvexCore,
+    axis: Vector,
+    overlap: f32,
+) -> Option<CollisionInfo> {
+    let depth = overlap + first.radius + second.radius;
+
+    //sharp shapes only touching each other
+    if depth <= 0.0 {
+        return None;
+    }
+
+    let normal = axis;
+    let contact_point = (first.support_point(normal) + second.support_point(-normal)) * 0.5;
+
+    Some(CollisionInfo::new(normal, depth, contact_point))
+}
+
+fn separated_cores_collision(first: &ConvexCore, second: &ConvexCore) -> Option<CollisionInfo> {
+    let (first_point, second_point) = closest_points(first, second);
+    let difference = second_point - first_point;
+    let distance = difference.length();
+    let sum_of_radii = first.radius + second.radius;
+
+    if distance >= sum_of_radii {
+        return None;
+    }
+
+    let normal = difference.normal().unwrap_or(Vector::new(1.0, 0.0));
+    let depth = sum_of_radii - distance;
+    let contact_point =
+        (first_point + normal * first.radius + second_point - normal * second.radius) * 0.5;
+
+    Some(CollisionInfo::new(normal, depth, contact_point))
+}
+
+/// Closest points of two cores which don't intersect. For disjoint convex shapes one of them
+/// is always a vertex, so checking every vertex against every edge of the other is enough.
+fn closest_points(first: &ConvexCore, second: &ConvexCore) -> (Point, Point) {
+    let mut best = (first.vertices[0], second.vertices[0]);
+    let mut best_distance = f32::MAX;
+
+    for vertex in first.get_vertices() {
+        for (start, end) in second.edges() {
+            let on_edge = closest_point_on_segment(*vertex, start, end);
+            let distance = (on_edge - *vertex).length();
+            if distance < best_distance {
+                best_distance = distance;
+                best = (*vertex, on_edge);
+            }
+        }
+    }
+
+    for vertex in second.get_vertices() {
+        for (start, end) in first.edges() {
+            let on_edge = closest_point_on_segment(*vertex, start, end);
+            let distance = (on_edge - *vertex).length();
+            if distance < best_distance {
+                best_distance = distance;
+                best = (on_edge, *vertex);
+            }
+        }
+    }
+
+    best
+}
+
+fn closest_point_on_segment(point: Point, start: Point, end: Point) -> Point {
+    let segment = end - start;
+    let length_squared = segment.dot(segment);
+
+    if length_squared <= 0.0 {
+        return start;
+    }
+
+    let t = ((point - start).dot(segment) / length_squared).clamp(0.0, 1.0);
+    start + segment * t
+}
+
+pub fn cross(first: Vector, second: Vector) -> f32 {
+    first.get_x() * second.get_y() - first.get_y() * second.get_x()
+}
+
+/// Fraction of the motion at which the point enters the circle, it has to start outside.
+fn ray_to_circle(origin: Point, motion: Vector, center: Point, radius: f32) -> Option<f32> {
+    let offset = origin - center;
+    let a = motion.dot(motion);
+    let b = 2.0 * offset.dot(motion);
+    let c = offset.dot(offset) - radius * radius;
+
+    let discriminant = b * b - 4.0 * a * c;
+    if a <= 0.0 || discriminant < 0.0 {
+        return None;
+    }
+    Some((-b - discriminant.sqrt()) / (2.0 * a))
+}
+
+/// Fraction of the motion at which the point crosses the segment, parallel ones are never
+/// crossed.
+fn ray_to_segment(origin: Point, motion: Vector, start: Point, end: Point) -> Option<f32> {
+    let segment = end - start;
+    let denominator = cross(motion, segment);
+    if denominator == 0.0 {
+        return None;
+    }
+
+    let offset = start - origin;
+    let fraction_along_segment = cross(offset, motion) / denominator;
+    if !(0.0..=1.0).contains(&fraction_along_segment) {
+        return None;
+    }
+    Some(cross(offset, segment) / denominator)
+}
@@ -1,9 +1,17 @@
-use std::vec;
+pub mod BroadPhase;
+pub mod DebugDraw;
+pub mod LayerMatrix;
+pub mod Queries;
+pub mod Solver;
+#[cfg(test)]
+mod Tests;
 
-use crate::MathUtilities::{Position, Vector};
+use std::{collections::HashMap, vec};
+
+use self::{BroadPhase::SpatialHash, LayerMatrix::CollisionLayerMatrix};
 
 use super::{
-    Circle, Collidable, Collider, CollisionInfo, CollisionMask, CollisionShape, Rectangle,
+    Collidable, Collider, CollisionInfo, CollisionMask, CollisionResponse, ContactPhase, EntityId,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -12,57 +20,191 @@ pub struct Pair<T> {
     pub second: T,
 }
 
+#[derive(Clone, Copy)]
 pub struct SymulationCollisionInfo {
+    /// For `Exit` these are the indices from the last detection which saw the contact.
     pub collided_objects_indices: Pair<usize>,
+    pub collided_objects_ids: Pair<EntityId>,
     pub info: CollisionInfo,
     pub masks: Pair<CollisionMask>,
+    pub response: CollisionResponse,
+    pub phase: ContactPhase,
+}
+
+/// State of a collidable when the last detection ran, kept for the spatial queries.
+#[derive(Clone, Copy)]
+pub struct SymulationObject {
+    pub id: EntityId,
+    pub collider: Collider,
+    pub mask: CollisionMask,
 }
 
 pub struct WordSymulation {
     pub symulation_collisions_info: Vec<SymulationCollisionInfo>,
+    pub layer_matrix: CollisionLayerMatrix,
+    pub solver_iterations: usize,
+    objects: Vec<SymulationObject>,
+    broad_phase: SpatialHash,
+    checked_pair_count: usize,
+    //contacts found by the last detection, keyed by the ids with the smaller one first
+    contacts: HashMap<(EntityId, EntityId), SymulationCollisionInfo>,
 }
 
 impl WordSymulation {
+    pub const DEFAULT_CELL_SIZE: f32 = 100.0;
+
     pub fn new() -> Self {
+        Self::with_cell_size(Self::DEFAULT_CELL_SIZE)
+    }
+
+    pub fn with_cell_size(cell_size: f32) -> Self {
         Self {
             symulation_collisions_info: vec![],
+            layer_matrix: CollisionLayerMatrix::default(),
+            solver_iterations: Self::DEFAULT_SOLVER_ITERATIONS,
+            objects: vec![],
+            broad_phase: SpatialHash::new(cell_size),
+            checked_pair_count: 0,
+            contacts: HashMap::new(),
         }
     }
 
     pub fn collision_detection(&mut self, collidables: &Vec<&mut dyn Collidable>) {
-        for i in 0..collidables.len() - 1 {
-            for j in i + 1..collidables.len() {
-                let first = collidables.get(i).unwrap();
-                let second = collidables.get(j).unwrap();
-                let did_collide = Collider::collide(first.get_collider(), second.get_collider());
-
-                match did_collide {
-                    Some(collision_info) => {
-                        let collided_objects_indices = Pair {
-                            first: i,
-                            second: j,
-                        };
-
-                        let collided_objects_masks = Pair {
-                            first: first.get_mask(),
-                            second: second.get_mask(),
-                        };
-
-                        let symulation_collision_info = SymulationCollisionInfo {
-                            collided_objects_indices: collided_objects_indices,
-                            info: collision_info,
-                            masks: collided_objects_masks,
-                        };
-
-                        self.symulation_collisions_info
-                            .push(symulation_collision_info);
-                    }
-                    None => { /* do nothing */ }
-                }
+        self.take_snapshot(collidables);
+
+        let candidate_pairs = self.broad_phase.candidate_pairs();
+        self.checked_pair_count = candidate_pairs.len();
+        for pair in candidate_pairs {
+            self.narrow_phase(pair.first, pair.second);
+        }
+
+        self.update_contacts();
+    }
+
+    //every pair against every other, the broad phase is checked against it
+    #[cfg(test)]
+    pub fn collision_detection_brute_force(&mut self, collidables: &Vec<&mut dyn Collidable>) {
+        self.take_snapshot(collidables);
+
+        let object_count = self.objects.len();
+        self.checked_pair_count = object_count * object_count.saturating_sub(1) / 2;
+        for i in 0..self.objects.len() {
+            for j in i + 1..self.objects.len() {
+                self.narrow_phase(i, j);
+            }
+        }
+
+        self.update_contacts();
+    }
+
+    fn take_snapshot(&mut self, collidables: &Vec<&mut dyn Collidable>) {
+        self.objects = collidables
+            .iter()
+            .map(|collidable| SymulationObject {
+                id: collidable.get_id(),
+                collider: collidable.get_collider(),
+                mask: collidable.get_mask(),
+            })
+            .collect();
+
+        //the queries need the grid even when the detection itself doesn't use it
+        self.broad_phase.clear();
+        for (index, object) in self.objects.iter().enumerate() {
+            self.broad_phase
+                .insert(index, object.collider.bounding_box());
+        }
+    }
+
+    fn narrow_phase(&mut self, i: usize, j: usize) {
+        let first = self.objects[i];
+        let second = self.objects[j];
+
+        let response = match self.layer_matrix.get_interaction(first.mask, second.mask) {
+            Some(response) => response,
+            None => return,
+        };
+
+        let did_collide = Collider::collide(first.collider, second.collider);
+
+        match did_collide {
+            Some(collision_info) => {
+                let collided_objects_indices = Pair {
+                    first: i,
+                    second: j,
+                };
+
+                let collided_objects_ids = Pair {
+                    first: first.id,
+                    second: second.id,
+                };
+
+                let collided_objects_masks = Pair {
+                    first: first.mask,
+                    second: second.mask,
+                };
+
+                let symulation_collision_info = SymulationCollisionInfo {
+                    collided_objects_indices,
+                    collided_objects_ids,
+                    info: collision_info,
+                    masks: collided_objects_masks,
+                    response,
+                    //settled by update_contacts once every pair is known
+                    phase: ContactPhase::Enter,
+                };
+
+                self.symulation_collisions_info
+                    .push(symulation_collision_info);
             }
+            None => { /* do nothing */ }
         }
     }
 
+    /// Marks the contacts which already existed during the previous detection as `Stay` and
+    /// adds an `Exit` for each of those which are gone.
+    fn update_contacts(&mut self) {
+        let mut previous_contacts = std::mem::take(&mut self.contacts);
+
+        for symulation_collision_info in &mut self.symulation_collisions_info {
+            let key = Self::contact_key(symulation_collision_info.collided_objects_ids);
+
+            symulation_collision_info.phase = match previous_contacts.remove(&key) {
+                Some(_) => ContactPhase::Stay,
+                None => ContactPhase::Enter,
+            };
+            self.contacts.insert(key, *symulation_collision_info);
+        }
+
+        let mut exits: Vec<SymulationCollisionInfo> = previous_contacts
+            .into_values()
+            .map(|contact| SymulationCollisionInfo {
+                phase: ContactPhase::Exit,
+                ..contact
+            })
+            .collect();
+        //hash map order would make the reactions order random
+        exits.sort_by_key(|exit| Self::contact_key(exit.collided_objects_ids));
+
+        self.symulation_collisions_info.extend(exits);
+    }
+
+    /// Pairs the last detection tested for a collision.
+    pub fn get_checked_pair_count(&self) -> usize {
+        self.checked_pair_count
+    }
+
+    /// Pairs touching each other since the last detection.
+    pub fn get_colliding_pair_count(&self) -> usize {
+        self.contacts.len()
+    }
+
+    fn contact_key(ids: Pair<EntityId>) -> (EntityId, EntityId) {
+        (
+            EntityId::min(ids.first, ids.second),
+            EntityId::max(ids.first, ids.second),
+        )
+    }
+
     pub fn clear_collisions(&mut self) {
         self.symulation_collisions_info.clear();
     }
@@ -78,18 +220,41 @@ impl WordSymulation {
         collidables: &mut Vec<&mut dyn Collidable>,
         symulation_collisions_info: &Vec<SymulationCollisionInfo>
     ) {
+        //exits can refer to collidables which were removed since, so everything goes by id
+        let indices_by_id: HashMap<EntityId, usize> = collidables
+            .iter()
+            .enumerate()
+            .map(|(index, collidable)| (collidable.get_id(), index))
+            .collect();
+
         for symulation_collision_info in symulation_collisions_info {
-            let collision_pair = symulation_collision_info.collided_objects_indices;
-            let first = collidables.get_mut(collision_pair.first).unwrap();
+            let collision_pair = symulation_collision_info.collided_objects_ids;
+            let first = match indices_by_id.get(&collision_pair.first) {
+                Some(index) => collidables.get_mut(*index).unwrap(),
+                None => continue,
+            };
             
-            first.react_to_collision(symulation_collision_info.info, symulation_collision_info.masks.second);
+            first.react_to_collision(
+                symulation_collision_info.info,
+                symulation_collision_info.masks.second,
+                symulation_collision_info.response,
+                symulation_collision_info.phase,
+            );
         }
 
         for symulation_collision_info in symulation_collisions_info {
-            let collision_pair = symulation_collision_info.collided_objects_indices;
-            let second = collidables.get_mut(collision_pair.second).unwrap();
+            let collision_pair = symulation_collision_info.collided_objects_ids;
+            let second = match indices_by_id.get(&collision_pair.second) {
+                Some(index) => collidables.get_mut(*index).unwrap(),
+                None => continue,
+            };
             
-            second.react_to_collision(symulation_collision_info.info.symetrical(), symulation_collision_info.masks.first);
+            second.react_to_collision(
+                symulation_collision_info.info.symetrical(),
+                symulation_collision_info.masks.first,
+                symulation_collision_info.response,
+                symulation_collision_info.phase,
+            );
         }
 
     }
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use crate::{
+    CollisionSystem::BoundingBox,
+    MathUtilities::{Position, Vector},
+};
+
+use super::Pair;
+
+type Cell = (i32, i32);
+
+pub struct SpatialHash {
+    cell_size: f32,
+    cells: HashMap<Cell, Vec<usize>>,
+    bounds: Option<BoundingBox>,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.bounds = None;
+    }
+
+    pub fn get_cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Box around everything inserted since the last clear.
+    pub fn get_bounds(&self) -> Option<BoundingBox> {
+        self.bounds
+    }
+
+    pub fn insert(&mut self, index: usize, bounding_box: BoundingBox) {
+        self.bounds = match self.bounds {
+            Some(bounds) => Some(bounds.merged(&bounding_box)),
+            None => Some(bounding_box),
+        };
+
+        let (min_cell, max_cell) = self.cells_range(bounding_box);
+
+        for x in min_cell.0..=max_cell.0 {
+            for y in min_cell.1..=max_cell.1 {
+                self.cells.entry((x, y)).or_default().push(index);
+            }
+        }
+    }
+
+    /// Every pair of indices sharing at least one cell, each reported once and ordered the same
+    /// way as the brute force loop (by first index, then by second).
+    pub fn candidate_pairs(&self) -> Vec<Pair<usize>> {
+        let mut pairs: Vec<(usize, usize)> = vec![];
+
+        for indices in self.cells.values() {
+            for i in 0..indices.len() {
+                for j in i + 1..indices.len() {
+                    let first = usize::min(indices[i], indices[j]);
+                    let second = usize::max(indices[i], indices[j]);
+                    pairs.push((first, second));
+                }
+            }
+        }
+
+        pairs.sort_unstable();
+        pairs.dedup();
+
+        pairs
+            .into_iter()
+            .map(|(first, second)| Pair { first, second })
+            .collect()
+    }
+
+    /// Indices from every cell touched by the box, each reported once.
+    pub fn query(&self, bounding_box: BoundingBox) -> Vec<usize> {
+        let bounds = match self.bounds {
+            Some(bounds) => bounds,
+            None => return vec![],
+        };
+
+        //no need to visit the empty cells around everything that was inserted
+        let clamped_box = BoundingBox::new(
+            Vector::new(
+                f32::max(bounding_box.min.get_x(), bounds.min.get_x()),
+                f32::max(bounding_box.min.get_y(), bounds.min.get_y()),
+            ),
+            Vector::new(
+                f32::min(bounding_box.max.get_x(), bounds.max.get_x()),
+                f32::min(bounding_box.max.get_y(), bounds.max.get_y()),
+            ),
+        );
+
+        if clamped_box.min.get_x() > clamped_box.max.get_x()
+            || clamped_box.min.get_y() > clamped_box.max.get_y()
+        {
+            return vec![];
+        }
+
+        let (min_cell, max_cell) = self.cells_range(clamped_box);
+        let mut indices: Vec<usize> = vec![];
+
+        for x in min_cell.0..=max_cell.0 {
+            for y in min_cell.1..=max_cell.1 {
+                if let Some(cell) = self.cells.get(&(x, y)) {
+                    indices.extend(cell);
+                }
+            }
+        }
+
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+    }
+
+    /// Occupied cells crossed by the ray, in order, with the distance at which the ray enters
+    /// each of them. `direction` has to be normalized.
+    pub fn cells_along_ray(
+        &self,
+        origin: Position,
+        direction: Vector,
+        max_distance: f32,
+    ) -> Vec<(f32, &Vec<usize>)> {
+        //past everything that was inserted there is nothing left to find, which also keeps an
+        //infinite ray from walking forever
+        let exit_distance = match self
+            .bounds
+            .and_then(|bounds| bounds.get_ray_exit_distance(origin, direction))
+        {
+            Some(exit_distance) => exit_distance,
+            None => return vec![],
+        };
+        let max_distance = f32::min(max_distance, exit_distance);
+
+        let mut cell = self.cell_of(origin.get_x(), origin.get_y());
+
+        let (step_x, mut next_x, delta_x) =
+            self.ray_axis_steps(origin.get_x(), direction.get_x(), cell.0);
+        let (step_y, mut next_y, delta_y) =
+            self.ray_axis_steps(origin.get_y(), direction.get_y(), cell.1);
+
+        let mut crossed_cells = vec![];
+        let mut entry_distance = 0.0f32;
+
+        while entry_distance <= max_distance {
+            if let Some(indices) = self.cells.get(&cell) {
+                crossed_cells.push((entry_distance, indices));
+            }
+
+            if next_x < next_y {
+                entry_distance = next_x;
+                next_x += delta_x;
+                cell.0 += step_x;
+            } else {
+                entry_distance = next_y;
+                next_y += delta_y;
+                cell.1 += step_y;
+            }
+        }
+
+        crossed_cells
+    }
+
+    /// Step direction, distance to the first cell border and distance between borders.
+    fn ray_axis_steps(&self, origin: f32, direction: f32, cell: i32) -> (i32, f32, f32) {
+        if direction > 0.0 {
+            let border = (cell + 1) as f32 * self.cell_size;
+            (1, (border - origin) / direction, self.cell_size / direction)
+        } else if direction < 0.0 {
+            let border = cell as f32 * self.cell_size;
+            (-1, (border - origin) / direction, -self.cell_size / direction)
+        } else {
+            (0, f32::INFINITY, f32::INFINITY)
+        }
+    }
+
+    pub fn get_occupied_cells(&self) -> Vec<BoundingBox> {
+        self.cells
+            .keys()
+            .map(|(x, y)| {
+                let min = Vector::new(*x as f32, *y as f32) * self.cell_size;
+                BoundingBox::new(min, min + Vector::new(self.cell_size, self.cell_size))
+            })
+            .collect()
+    }
+
+    fn cells_range(&self, bounding_box: BoundingBox) -> (Cell, Cell) {
+        (
+            self.cell_of(bounding_box.min.get_x(), bounding_box.min.get_y()),
+            self.cell_of(bounding_box.max.get_x(), bounding_box.max.get_y()),
+        )
+    }
+
+    fn cell_of(&self, x: f32, y: f32) -> Cell {
+        (
+            (x / self.cell_size).floor() as i32,
+            (y / self.cell_size).floor() as i32,
+        )
+    }
+}
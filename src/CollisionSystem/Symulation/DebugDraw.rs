@@ -0,0 +1,79 @@
+use sfml::graphics::Color;
+
+use crate::{
+    CollisionSystem::{CollisionMask, ContactPhase},
+    MathUtilities::{Point, Vector},
+    Objects::Interfaces::Renderer,
+};
+
+use super::WordSymulation;
+
+const NORMAL_LENGTH: f32 = 20.0;
+
+const GRID_COLOR: Color = Color::rgba(90, 90, 90, 160);
+const CONTACT_NORMAL_COLOR: Color = Color::WHITE;
+//hits found along the motion, they have no depth
+const SWEPT_CONTACT_NORMAL_COLOR: Color = Color::MAGENTA;
+const CONTACT_DEPTH_COLOR: Color = Color::YELLOW;
+
+//draws what the last detection saw, which is what the collidables were reacting to
+impl WordSymulation {
+    pub fn draw_debug(&self, renderer: &mut dyn Renderer) {
+        self.draw_debug_grid(renderer);
+
+        for object in &self.objects {
+            let outline = object.collider.get_outline();
+            Self::draw_loop(renderer, &outline, Self::mask_debug_color(object.mask));
+        }
+
+        self.draw_debug_contacts(renderer);
+    }
+
+    fn draw_debug_grid(&self, renderer: &mut dyn Renderer) {
+        for cell in self.broad_phase.get_occupied_cells() {
+            let corners = [
+                cell.min,
+                Vector::new(cell.max.get_x(), cell.min.get_y()),
+                cell.max,
+                Vector::new(cell.min.get_x(), cell.max.get_y()),
+            ];
+            Self::draw_loop(renderer, &corners, GRID_COLOR);
+        }
+    }
+
+    fn draw_debug_contacts(&self, renderer: &mut dyn Renderer) {
+        for symulation_collision_info in &self.symulation_collisions_info {
+            if symulation_collision_info.phase == ContactPhase::Exit {
+                continue;
+            }
+
+            let info = symulation_collision_info.info;
+            let normal_end = info.contact_point + info.normal * NORMAL_LENGTH;
+            let depth_end = info.contact_point + info.get_collision_depth();
+
+            let normal_color = match info.time_of_impact {
+                Some(_) => SWEPT_CONTACT_NORMAL_COLOR,
+                None => CONTACT_NORMAL_COLOR,
+            };
+            renderer.draw_line_strip(&[info.contact_point, normal_end], normal_color);
+            renderer.draw_line_strip(&[info.contact_point, depth_end], CONTACT_DEPTH_COLOR);
+        }
+    }
+
+    fn draw_loop(renderer: &mut dyn Renderer, points: &[Point], color: Color) {
+        let mut closed_points = points.to_vec();
+        if let Some(first) = points.first() {
+            closed_points.push(*first);
+        }
+
+        renderer.draw_line_strip(&closed_points, color);
+    }
+
+    fn mask_debug_color(mask: CollisionMask) -> Color {
+        match mask {
+            CollisionMask::Player => Color::GREEN,
+            CollisionMask::Weapon => Color::CYAN,
+            CollisionMask::Enemy => Color::RED,
+        }
+    }
+}
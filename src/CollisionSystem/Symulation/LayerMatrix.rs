@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use crate::CollisionSystem::{CollisionMask, CollisionResponse};
+
+/// Says which collision layers interact and how. Pairs without an entry are never tested.
+pub struct CollisionLayerMatrix {
+    interactions: HashMap<(CollisionMask, CollisionMask), CollisionResponse>,
+}
+
+impl CollisionLayerMatrix {
+    pub fn new() -> Self {
+        Self {
+            interactions: HashMap::new(),
+        }
+    }
+
+    pub fn set_interaction(
+        &mut self,
+        first: CollisionMask,
+        second: CollisionMask,
+        response: CollisionResponse,
+    ) {
+        self.interactions.insert((first, second), response);
+        self.interactions.insert((second, first), response);
+    }
+
+    pub fn get_interaction(
+        &self,
+        first: CollisionMask,
+        second: CollisionMask,
+    ) -> Option<CollisionResponse> {
+        self.interactions.get(&(first, second)).copied()
+    }
+}
+
+impl Default for CollisionLayerMatrix {
+    fn default() -> Self {
+        let mut matrix = Self::new();
+        matrix.set_interaction(
+            CollisionMask::Player,
+            CollisionMask::Player,
+            CollisionResponse::Solid,
+        );
+        matrix.set_interaction(
+            CollisionMask::Player,
+            CollisionMask::Enemy,
+            CollisionResponse::Solid,
+        );
+        matrix.set_interaction(
+            CollisionMask::Enemy,
+            CollisionMask::Enemy,
+            CollisionResponse::Solid,
+        );
+        matrix.set_interaction(
+            CollisionMask::Weapon,
+            CollisionMask::Enemy,
+            CollisionResponse::Trigger,
+        );
+        matrix
+    }
+}
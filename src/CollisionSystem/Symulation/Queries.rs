@@ -0,0 +1,224 @@
+use crate::{
+    CollisionSystem::{
+        BoundingBox, Circle, Collider, CollisionInfo, CollisionMask, CollisionShape, EntityId,
+        Rectangle,
+    },
+    MathUtilities::{Point, Position, Vector},
+};
+
+use super::WordSymulation;
+
+/// Collidable overlapping the queried area. `info` is built the same way as in the detection,
+/// with the query shape as the first collider.
+#[derive(Clone, Copy)]
+pub struct OverlapHit {
+    pub index: usize,
+    pub id: EntityId,
+    pub collider: Collider,
+    pub info: CollisionInfo,
+}
+
+#[derive(Clone, Copy)]
+pub struct NearestHit {
+    pub index: usize,
+    pub id: EntityId,
+    pub collider: Collider,
+    /// Distance between the queried position and the collider position.
+    pub distance: f32,
+}
+
+#[derive(Clone, Copy)]
+pub struct RaycastHit {
+    pub index: usize,
+    pub id: EntityId,
+    pub collider: Collider,
+    /// Distance along the ray from its origin.
+    pub distance: f32,
+    pub point: Point,
+    /// Surface normal of the hit collider, pointing back towards the ray origin.
+    pub normal: Vector,
+}
+
+//every query works on the colliders stored during the last detection, so the indices are the same
+//as the ones of the collidables passed to it
+impl WordSymulation {
+    pub fn query_circle(
+        &self,
+        center: Position,
+        radius: f32,
+        mask: CollisionMask,
+    ) -> Vec<OverlapHit> {
+        let query_collider = Collider::new(CollisionShape::Circle(Circle::new(radius)), center);
+        self.query_overlap(query_collider, mask)
+    }
+
+    pub fn query_rect(
+        &self,
+        center: Position,
+        rectangle: Rectangle,
+        mask: CollisionMask,
+    ) -> Vec<OverlapHit> {
+        let query_collider = Collider::new(CollisionShape::Rectangle(rectangle), center);
+        self.query_overlap(query_collider, mask)
+    }
+
+    fn query_overlap(&self, query_collider: Collider, mask: CollisionMask) -> Vec<OverlapHit> {
+        self.broad_phase
+            .query(query_collider.bounding_box())
+            .into_iter()
+            .filter(|index| self.objects[*index].mask == mask)
+            .filter_map(|index| {
+                let collider = Self::without_motion(self.objects[index].collider);
+                Collider::collide(query_collider, collider).map(|info| OverlapHit {
+                    index,
+                    id: self.objects[index].id,
+                    collider,
+                    info,
+                })
+            })
+            .collect()
+    }
+
+    //this and k_nearest aren't used by the level yet, the coming weapons aim without a filter
+    #[allow(dead_code)]
+    pub fn nearest(&self, position: Position, mask: CollisionMask) -> Option<NearestHit> {
+        self.nearest_matching(position, mask, |_| true)
+    }
+
+    /// Closest collidable with the given mask accepted by `is_accepted`. The search stops at the
+    /// first searched area holding an accepted one.
+    pub fn nearest_matching(
+        &self,
+        position: Position,
+        mask: CollisionMask,
+        is_accepted: impl Fn(&NearestHit) -> bool,
+    ) -> Option<NearestHit> {
+        self.k_nearest_matching(position, mask, 1, is_accepted).pop()
+    }
+
+    /// Up to `count` collidables with the given mask, closest first.
+    #[allow(dead_code)]
+    pub fn k_nearest(
+        &self,
+        position: Position,
+        mask: CollisionMask,
+        count: usize,
+    ) -> Vec<NearestHit> {
+        self.k_nearest_matching(position, mask, count, |_| true)
+    }
+
+    fn k_nearest_matching(
+        &self,
+        position: Position,
+        mask: CollisionMask,
+        count: usize,
+        is_accepted: impl Fn(&NearestHit) -> bool,
+    ) -> Vec<NearestHit> {
+        let bounds = match self.broad_phase.get_bounds() {
+            Some(bounds) => bounds,
+            None => return vec![],
+        };
+
+        if count == 0 {
+            return vec![];
+        }
+
+        //grows the searched area until it certainly holds the closest ones or covers everything
+        let mut radius = self.broad_phase.get_cell_size();
+        loop {
+            let searched_box = BoundingBox::around(position, Vector::new(radius, radius));
+
+            let mut hits: Vec<NearestHit> = self
+                .broad_phase
+                .query(searched_box)
+                .into_iter()
+                .filter(|index| self.objects[*index].mask == mask)
+                .map(|index| {
+                    let collider = self.objects[index].collider;
+                    NearestHit {
+                        index,
+                        id: self.objects[index].id,
+                        collider,
+                        distance: (collider.position - position).length(),
+                    }
+                })
+                .filter(&is_accepted)
+                .collect();
+
+            hits.sort_by(|first, second| first.distance.total_cmp(&second.distance));
+            hits.truncate(count);
+
+            let are_closest_found = hits.len() == count && hits[count - 1].distance <= radius;
+            if are_closest_found || searched_box.contains(&bounds) {
+                return hits;
+            }
+
+            radius *= 2.0;
+        }
+    }
+
+    /// First collidable with the given mask along the ray. `direction` doesn't have to be
+    /// normalized, an infinite `max_distance` reaches every collidable.
+    pub fn raycast(
+        &self,
+        origin: Position,
+        direction: Vector,
+        max_distance: f32,
+        mask: CollisionMask,
+    ) -> Option<RaycastHit> {
+        let direction = direction.normal()?;
+        //the ray is cut where it leaves the colliders, the motion of the sweep has to be finite
+        let exit_distance = self
+            .broad_phase
+            .get_bounds()?
+            .get_ray_exit_distance(origin, direction)?;
+        let max_distance = f32::min(max_distance, exit_distance);
+        let mut best: Option<RaycastHit> = None;
+
+        for (entry_distance, indices) in
+            self.broad_phase
+                .cells_along_ray(origin, direction, max_distance)
+        {
+            //cells further than the closest hit can't hold anything closer
+            if best.is_some_and(|hit| hit.distance < entry_distance) {
+                break;
+            }
+
+            for index in indices {
+                if self.objects[*index].mask != mask {
+                    continue;
+                }
+
+                let collider = Self::without_motion(self.objects[*index].collider);
+                let sweep = match Collider::swept_circle_collision(
+                    origin,
+                    0.0,
+                    direction * max_distance,
+                    collider,
+                ) {
+                    Some(sweep) => sweep,
+                    None => continue,
+                };
+
+                let distance = sweep.time_of_impact * max_distance;
+                if best.is_none_or(|hit| distance < hit.distance) {
+                    best = Some(RaycastHit {
+                        index: *index,
+                        id: self.objects[*index].id,
+                        collider,
+                        distance,
+                        point: origin + direction * distance,
+                        normal: sweep.normal,
+                    });
+                }
+            }
+        }
+
+        best
+    }
+
+    //queries look at where the colliders are, not at the way they came from
+    fn without_motion(collider: Collider) -> Collider {
+        collider.with_motion(Vector::default())
+    }
+}
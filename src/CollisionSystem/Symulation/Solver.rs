@@ -0,0 +1,82 @@
+use crate::{
+    CollisionSystem::{Collidable, Collider, CollisionResponse, ContactPhase},
+    MathUtilities::Vector,
+};
+
+use super::{Pair, WordSymulation};
+
+impl WordSymulation {
+    pub const DEFAULT_SOLVER_ITERATIONS: usize = 4;
+
+    /// Moves the collidables out of the solid contacts found by the last detection. Each
+    /// iteration relaxes every contact once, so crowds settle over a few of them instead of
+    /// everyone being pushed by the sum of its overlaps.
+    pub fn resolve_solid_collisions(&self, collidables: &mut [&mut dyn Collidable]) {
+        let solid_contacts: Vec<Pair<usize>> = self
+            .symulation_collisions_info
+            .iter()
+            .filter(|info| {
+                info.response == CollisionResponse::Solid && info.phase != ContactPhase::Exit
+            })
+            .map(|info| info.collided_objects_indices)
+            .collect();
+
+        if solid_contacts.is_empty() {
+            return;
+        }
+
+        let mut offsets = vec![Vector::default(); self.objects.len()];
+
+        for _ in 0..self.solver_iterations {
+            for contact in &solid_contacts {
+                let first = self.solver_collider(contact.first, &offsets);
+                let second = self.solver_collider(contact.second, &offsets);
+
+                let info = match Collider::collide(first, second) {
+                    Some(info) => info,
+                    None => continue,
+                };
+
+                let (first_share, second_share) = Self::correction_shares(&first, &second);
+                offsets[contact.first] += -info.get_collision_depth() * first_share;
+                offsets[contact.second] += info.get_collision_depth() * second_share;
+            }
+        }
+
+        for (index, offset) in offsets.into_iter().enumerate() {
+            if offset.get_x() != 0.0 || offset.get_y() != 0.0 {
+                collidables[index].translate(offset);
+            }
+        }
+    }
+
+    fn solver_collider(&self, index: usize, offsets: &[Vector]) -> Collider {
+        let mut collider = self.objects[index].collider.with_motion(Vector::default());
+        collider.position += offsets[index];
+        collider
+    }
+
+    /// Parts of the overlap taken by each side of the contact.
+    fn correction_shares(first: &Collider, second: &Collider) -> (f32, f32) {
+        if first.pushback_priority > second.pushback_priority {
+            return (0.0, 1.0);
+        }
+        if first.pushback_priority < second.pushback_priority {
+            return (1.0, 0.0);
+        }
+
+        let first_inverse_mass = 1.0 / first.mass;
+        let second_inverse_mass = 1.0 / second.mass;
+        let sum_of_inverse_masses = first_inverse_mass + second_inverse_mass;
+
+        //two immovable colliders stay where they are
+        if sum_of_inverse_masses <= 0.0 {
+            return (0.0, 0.0);
+        }
+
+        (
+            first_inverse_mass / sum_of_inverse_masses,
+            second_inverse_mass / sum_of_inverse_masses,
+        )
+    }
+}
@@ -0,0 +1,648 @@
+use std::time::Instant;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::*;
+use crate::{
+    CollisionSystem::{BoundingBox, Capsule, Circle, CollisionShape, OrientedRectangle, Rectangle},
+    MathUtilities::{Position, Vector},
+};
+
+struct TestCollidable {
+    id: EntityId,
+    collider: Collider,
+    mask: CollisionMask,
+    phases: Vec<ContactPhase>,
+}
+
+impl TestCollidable {
+    fn new(collider: Collider, mask: CollisionMask) -> Self {
+        Self {
+            id: EntityId::new(),
+            collider,
+            mask,
+            phases: vec![],
+        }
+    }
+}
+
+impl Collidable for TestCollidable {
+    fn get_id(&self) -> EntityId {
+        self.id
+    }
+
+    fn get_collider(&self) -> Collider {
+        self.collider
+    }
+
+    fn get_mask(&self) -> CollisionMask {
+        self.mask
+    }
+
+    fn translate(&mut self, offset: Vector) {
+        self.collider.position += offset;
+    }
+
+    fn react_to_collision(
+        &mut self,
+        _info: CollisionInfo,
+        _other_mask: CollisionMask,
+        _response: CollisionResponse,
+        phase: ContactPhase,
+    ) {
+        self.phases.push(phase);
+    }
+}
+
+fn create_random_collidables(count: usize, seed: u64) -> Vec<TestCollidable> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    //keeps roughly the same crowd density for every count
+    let world_size = f32::sqrt(count as f32) * 60.0;
+
+    (0..count)
+        .map(|i| {
+            let position = Position::new(
+                rng.gen_range(0.0..world_size),
+                rng.gen_range(0.0..world_size),
+            );
+
+            let (shape, mask) = match i % 3 {
+                0 => (
+                    CollisionShape::Rectangle(Rectangle::new(40.0, 40.0)),
+                    CollisionMask::Enemy,
+                ),
+                1 => (
+                    CollisionShape::Circle(Circle::new(5.0)),
+                    CollisionMask::Weapon,
+                ),
+                _ => (
+                    CollisionShape::Circle(Circle::new(40.0)),
+                    CollisionMask::Player,
+                ),
+            };
+
+            TestCollidable::new(Collider::new(shape, position), mask)
+        })
+        .collect()
+}
+
+fn detect(
+    collidables: &mut [TestCollidable],
+    use_broad_phase: bool,
+) -> Vec<SymulationCollisionInfo> {
+    let mut symulation = WordSymulation::new();
+    let collidables: Vec<&mut dyn Collidable> = collidables
+        .iter_mut()
+        .map(|collidable| collidable as &mut dyn Collidable)
+        .collect();
+
+    if use_broad_phase {
+        symulation.collision_detection(&collidables);
+    } else {
+        symulation.collision_detection_brute_force(&collidables);
+    }
+
+    symulation.symulation_collisions_info
+}
+
+#[test]
+fn broad_phase_matches_brute_force_test() {
+    let mut collidables = create_random_collidables(500, 7);
+
+    let brute_force = detect(&mut collidables, false);
+    let broad_phase = detect(&mut collidables, true);
+
+    assert!(!brute_force.is_empty());
+    assert_eq!(brute_force.len(), broad_phase.len());
+
+    for (expected, actual) in brute_force.iter().zip(broad_phase.iter()) {
+        assert_eq!(
+            expected.collided_objects_indices.first,
+            actual.collided_objects_indices.first
+        );
+        assert_eq!(
+            expected.collided_objects_indices.second,
+            actual.collided_objects_indices.second
+        );
+        assert_eq!(
+            expected.info.get_collision_depth().get_x(),
+            actual.info.get_collision_depth().get_x()
+        );
+        assert_eq!(
+            expected.info.get_collision_depth().get_y(),
+            actual.info.get_collision_depth().get_y()
+        );
+    }
+}
+
+#[test]
+fn broad_phase_handles_no_collidables_test() {
+    assert!(detect(&mut [], true).is_empty());
+}
+
+//cargo test broad_phase_benchmark --release -- --ignored --nocapture
+#[test]
+#[ignore]
+fn broad_phase_benchmark() {
+    for count in [1000, 5000, 10000] {
+        let mut collidables = create_random_collidables(count, 42);
+
+        let timer = Instant::now();
+        let brute_force = detect(&mut collidables, false);
+        let brute_force_time = timer.elapsed();
+
+        let timer = Instant::now();
+        let broad_phase = detect(&mut collidables, true);
+        let broad_phase_time = timer.elapsed();
+
+        assert_eq!(brute_force.len(), broad_phase.len());
+        println!(
+            "{:>6} colliders: brute force {:>10.3?}, spatial hash {:>10.3?}, {} collisions",
+            count,
+            brute_force_time,
+            broad_phase_time,
+            broad_phase.len()
+        );
+    }
+}
+
+fn create_collidable_at(position: Position, mask: CollisionMask) -> TestCollidable {
+    TestCollidable::new(
+        Collider::new(CollisionShape::Circle(Circle::new(10.0)), position),
+        mask,
+    )
+}
+
+#[test]
+fn layer_matrix_skips_non_interacting_pairs_test() {
+    let mut collidables = vec![
+        create_collidable_at(Position::new(0.0, 0.0), CollisionMask::Weapon),
+        create_collidable_at(Position::new(5.0, 0.0), CollisionMask::Weapon),
+    ];
+
+    assert!(detect(&mut collidables, true).is_empty());
+    assert!(detect(&mut collidables, false).is_empty());
+}
+
+#[test]
+fn layer_matrix_reports_response_test() {
+    let mut collidables = vec![
+        create_collidable_at(Position::new(0.0, 0.0), CollisionMask::Weapon),
+        create_collidable_at(Position::new(5.0, 0.0), CollisionMask::Enemy),
+        create_collidable_at(Position::new(10.0, 0.0), CollisionMask::Enemy),
+    ];
+
+    let collisions = detect(&mut collidables, true);
+
+    assert_eq!(collisions.len(), 3);
+    assert_eq!(collisions[0].response, CollisionResponse::Trigger);
+    assert_eq!(collisions[1].response, CollisionResponse::Trigger);
+    assert_eq!(collisions[2].response, CollisionResponse::Solid);
+}
+
+fn detect_in(symulation: &mut WordSymulation, collidables: &mut [TestCollidable]) {
+    let collidables: Vec<&mut dyn Collidable> = collidables
+        .iter_mut()
+        .map(|collidable| collidable as &mut dyn Collidable)
+        .collect();
+
+    symulation.collision_detection(&collidables);
+}
+
+#[test]
+fn query_circle_returns_overlapping_collidables_with_mask_test() {
+    let mut collidables = vec![
+        create_collidable_at(Position::new(0.0, 0.0), CollisionMask::Enemy),
+        create_collidable_at(Position::new(25.0, 0.0), CollisionMask::Enemy),
+        create_collidable_at(Position::new(0.0, 25.0), CollisionMask::Weapon),
+        create_collidable_at(Position::new(300.0, 0.0), CollisionMask::Enemy),
+    ];
+    let mut symulation = WordSymulation::new();
+    detect_in(&mut symulation, &mut collidables);
+
+    let hits = symulation.query_circle(Position::new(0.0, 0.0), 20.0, CollisionMask::Enemy);
+    let ids: Vec<EntityId> = hits.iter().map(|hit| hit.id).collect();
+
+    assert_eq!(ids, vec![collidables[0].id, collidables[1].id]);
+    assert!((hits[1].info.depth - 5.0).abs() < 0.001);
+}
+
+#[test]
+fn query_rect_returns_overlapping_collidables_with_mask_test() {
+    let mut collidables = vec![
+        create_collidable_at(Position::new(0.0, 0.0), CollisionMask::Enemy),
+        create_collidable_at(Position::new(0.0, 55.0), CollisionMask::Enemy),
+        create_collidable_at(Position::new(45.0, 0.0), CollisionMask::Enemy),
+        create_collidable_at(Position::new(30.0, 0.0), CollisionMask::Weapon),
+    ];
+    let mut symulation = WordSymulation::new();
+    detect_in(&mut symulation, &mut collidables);
+
+    //wide and flat, reaches the one on the right but not the one above
+    let hits = symulation.query_rect(
+        Position::new(0.0, 0.0),
+        Rectangle::new(80.0, 20.0),
+        CollisionMask::Enemy,
+    );
+    let indices: Vec<usize> = hits.iter().map(|hit| hit.index).collect();
+
+    assert_eq!(indices, vec![0, 2]);
+    assert_eq!(hits[1].id, collidables[2].id);
+    assert_eq!(hits[1].collider.position, Position::new(45.0, 0.0));
+}
+
+#[test]
+fn nearest_matches_linear_scan_test() {
+    let mut collidables = create_random_collidables(300, 3);
+    let mut symulation = WordSymulation::new();
+    detect_in(&mut symulation, &mut collidables);
+
+    for position in [
+        Position::new(0.0, 0.0),
+        Position::new(500.0, 500.0),
+        Position::new(-2000.0, 300.0),
+    ] {
+        let expected = collidables
+            .iter()
+            .filter(|collidable| collidable.mask == CollisionMask::Enemy)
+            .map(|collidable| (collidable.collider.position - position).length())
+            .fold(f32::MAX, f32::min);
+
+        let nearest = symulation.nearest(position, CollisionMask::Enemy).unwrap();
+        assert_eq!(nearest.distance, expected);
+        assert_eq!(nearest.id, collidables[nearest.index].id);
+    }
+}
+
+#[test]
+fn nearest_matching_skips_rejected_collidables_test() {
+    let mut collidables = vec![
+        create_collidable_at(Position::new(20.0, 0.0), CollisionMask::Enemy),
+        create_collidable_at(Position::new(0.0, 90.0), CollisionMask::Enemy),
+        create_collidable_at(Position::new(-700.0, 0.0), CollisionMask::Enemy),
+        create_collidable_at(Position::new(0.0, 0.0), CollisionMask::Player),
+    ];
+    let mut symulation = WordSymulation::new();
+    detect_in(&mut symulation, &mut collidables);
+
+    let hit = symulation
+        .nearest_matching(Position::new(0.0, 0.0), CollisionMask::Enemy, |hit| {
+            hit.index != 0
+        })
+        .unwrap();
+    assert_eq!(hit.index, 1);
+
+    let hit = symulation
+        .nearest_matching(Position::new(0.0, 0.0), CollisionMask::Enemy, |hit| {
+            hit.index == 2
+        })
+        .unwrap();
+    assert_eq!(hit.id, collidables[2].id);
+    assert!((hit.distance - 700.0).abs() < 0.001);
+
+    assert!(symulation
+        .nearest_matching(Position::new(0.0, 0.0), CollisionMask::Enemy, |_| false)
+        .is_none());
+    assert!(symulation
+        .nearest(Position::new(0.0, 0.0), CollisionMask::Weapon)
+        .is_none());
+}
+
+#[test]
+fn k_nearest_returns_closest_first_test() {
+    let mut collidables = vec![
+        create_collidable_at(Position::new(400.0, 0.0), CollisionMask::Enemy),
+        create_collidable_at(Position::new(50.0, 0.0), CollisionMask::Enemy),
+        create_collidable_at(Position::new(10.0, 0.0), CollisionMask::Player),
+        create_collidable_at(Position::new(0.0, -150.0), CollisionMask::Enemy),
+    ];
+    let mut symulation = WordSymulation::new();
+    detect_in(&mut symulation, &mut collidables);
+
+    let hits = symulation.k_nearest(Position::new(0.0, 0.0), CollisionMask::Enemy, 5);
+    let ids: Vec<EntityId> = hits.iter().map(|hit| hit.id).collect();
+
+    assert_eq!(
+        ids,
+        vec![collidables[1].id, collidables[3].id, collidables[0].id]
+    );
+    assert!(symulation
+        .k_nearest(Position::new(0.0, 0.0), CollisionMask::Weapon, 1)
+        .is_empty());
+}
+
+#[test]
+fn raycast_returns_first_hit_along_ray_test() {
+    let mut collidables = vec![
+        create_collidable_at(Position::new(500.0, 0.0), CollisionMask::Enemy),
+        create_collidable_at(Position::new(200.0, 0.0), CollisionMask::Enemy),
+        create_collidable_at(Position::new(100.0, 0.0), CollisionMask::Weapon),
+        create_collidable_at(Position::new(200.0, 100.0), CollisionMask::Enemy),
+    ];
+    let mut symulation = WordSymulation::new();
+    detect_in(&mut symulation, &mut collidables);
+
+    let hit = symulation
+        .raycast(
+            Position::new(0.0, 0.0),
+            Vector::new(2.0, 0.0),
+            1000.0,
+            CollisionMask::Enemy,
+        )
+        .unwrap();
+
+    assert_eq!(hit.id, collidables[1].id);
+    assert_eq!(hit.index, 1);
+    assert_eq!(hit.collider.position, Position::new(200.0, 0.0));
+    assert!((hit.distance - 190.0).abs() < 0.001);
+    assert!((hit.point - Position::new(190.0, 0.0)).length() < 0.001);
+    assert!((hit.normal.get_x() + 1.0).abs() < 0.001);
+
+    assert!(symulation
+        .raycast(
+            Position::new(0.0, 0.0),
+            Vector::new(1.0, 0.0),
+            150.0,
+            CollisionMask::Enemy,
+        )
+        .is_none());
+    assert!(symulation
+        .raycast(
+            Position::new(0.0, 0.0),
+            Vector::new(-1.0, 0.0),
+            1000.0,
+            CollisionMask::Enemy,
+        )
+        .is_none());
+}
+
+#[test]
+fn raycast_hits_rotated_shape_test() {
+    let mut collidables = vec![TestCollidable::new(
+        Collider::new(
+            CollisionShape::OrientedRectangle(OrientedRectangle::new(40.0, 40.0)),
+            Position::new(-300.0, -300.0),
+        )
+        .with_rotation(45.0),
+        CollisionMask::Enemy,
+    )];
+    let mut symulation = WordSymulation::new();
+    detect_in(&mut symulation, &mut collidables);
+
+    let hit = symulation
+        .raycast(
+            Position::new(0.0, -300.0),
+            Vector::new(-1.0, 0.0),
+            1000.0,
+            CollisionMask::Enemy,
+        )
+        .unwrap();
+
+    //corner of the diamond sits half a diagonal away from the center
+    let half_diagonal = 20.0 * f32::sqrt(2.0);
+    assert!((hit.distance - (300.0 - half_diagonal)).abs() < 0.001);
+}
+
+#[test]
+fn raycast_without_max_distance_stops_past_colliders_test() {
+    let mut collidables = vec![
+        create_collidable_at(Position::new(200.0, 0.0), CollisionMask::Enemy),
+        TestCollidable::new(
+            Collider::new(
+                CollisionShape::Capsule(Capsule::new(40.0, 10.0)),
+                Position::new(0.0, 300.0),
+            )
+            .with_rotation(90.0),
+            CollisionMask::Enemy,
+        ),
+    ];
+    let mut symulation = WordSymulation::new();
+    detect_in(&mut symulation, &mut collidables);
+
+    let hit = symulation
+        .raycast(
+            Position::new(0.0, 0.0),
+            Vector::new(1.0, 0.0),
+            f32::INFINITY,
+            CollisionMask::Enemy,
+        )
+        .unwrap();
+    assert_eq!(hit.id, collidables[0].id);
+
+    //the end of the upright capsule is its radius past the end of its segment
+    let hit = symulation
+        .raycast(
+            Position::new(0.0, 0.0),
+            Vector::new(0.0, 1.0),
+            f32::INFINITY,
+            CollisionMask::Enemy,
+        )
+        .unwrap();
+    assert_eq!(hit.id, collidables[1].id);
+    assert!((hit.distance - 270.0).abs() < 0.001);
+    assert!((hit.normal.get_y() + 1.0).abs() < 0.001);
+
+    assert!(symulation
+        .raycast(
+            Position::new(0.0, 0.0),
+            Vector::new(-1.0, 0.0),
+            f32::INFINITY,
+            CollisionMask::Enemy,
+        )
+        .is_none());
+}
+
+fn step(symulation: &mut WordSymulation, collidables: &mut [TestCollidable]) {
+    let mut collidables: Vec<&mut dyn Collidable> = collidables
+        .iter_mut()
+        .map(|collidable| collidable as &mut dyn Collidable)
+        .collect();
+
+    symulation.collision_detection(&collidables);
+    symulation.react_to_collisionss(&mut collidables);
+    symulation.clear_collisions();
+}
+
+#[test]
+fn contact_phases_follow_contact_life_test() {
+    let mut collidables = vec![
+        create_collidable_at(Position::new(0.0, 0.0), CollisionMask::Weapon),
+        create_collidable_at(Position::new(100.0, 0.0), CollisionMask::Enemy),
+    ];
+    let mut symulation = WordSymulation::new();
+
+    step(&mut symulation, &mut collidables);
+    collidables[1].collider.position = Position::new(15.0, 0.0);
+    step(&mut symulation, &mut collidables);
+    step(&mut symulation, &mut collidables);
+    collidables[1].collider.position = Position::new(100.0, 0.0);
+    step(&mut symulation, &mut collidables);
+    step(&mut symulation, &mut collidables);
+
+    let expected = vec![ContactPhase::Enter, ContactPhase::Stay, ContactPhase::Exit];
+    assert_eq!(collidables[0].phases, expected);
+    assert_eq!(collidables[1].phases, expected);
+}
+
+#[test]
+fn contact_phases_use_ids_instead_of_indices_test() {
+    let mut collidables = vec![
+        create_collidable_at(Position::new(500.0, 0.0), CollisionMask::Enemy),
+        create_collidable_at(Position::new(0.0, 0.0), CollisionMask::Weapon),
+        create_collidable_at(Position::new(15.0, 0.0), CollisionMask::Enemy),
+    ];
+    let mut symulation = WordSymulation::new();
+
+    step(&mut symulation, &mut collidables);
+    //shifts the indices of the touching pair
+    collidables.remove(0);
+    step(&mut symulation, &mut collidables);
+    assert_eq!(
+        collidables[0].phases,
+        vec![ContactPhase::Enter, ContactPhase::Stay]
+    );
+
+    //the other side of an exit may not exist anymore
+    collidables.remove(1);
+    step(&mut symulation, &mut collidables);
+    assert_eq!(
+        collidables[0].phases,
+        vec![ContactPhase::Enter, ContactPhase::Stay, ContactPhase::Exit]
+    );
+}
+
+fn solve(symulation: &mut WordSymulation, collidables: &mut [TestCollidable]) {
+    let mut collidables: Vec<&mut dyn Collidable> = collidables
+        .iter_mut()
+        .map(|collidable| collidable as &mut dyn Collidable)
+        .collect();
+
+    symulation.collision_detection(&collidables);
+    symulation.resolve_solid_collisions(&mut collidables);
+    symulation.clear_collisions();
+}
+
+fn create_enemy_with(collider: Collider) -> TestCollidable {
+    TestCollidable::new(collider, CollisionMask::Enemy)
+}
+
+fn circle_at(x: f32) -> Collider {
+    Collider::new(
+        CollisionShape::Circle(Circle::new(10.0)),
+        Position::new(x, 0.0),
+    )
+}
+
+#[test]
+fn solver_splits_overlap_by_mass_test() {
+    let mut collidables = vec![
+        create_enemy_with(circle_at(0.0)),
+        create_enemy_with(circle_at(12.0)),
+        create_enemy_with(circle_at(100.0).with_mass(3.0)),
+        create_enemy_with(circle_at(112.0)),
+    ];
+    let mut symulation = WordSymulation::new();
+    solve(&mut symulation, &mut collidables);
+
+    let positions: Vec<f32> = collidables
+        .iter()
+        .map(|collidable| collidable.collider.position.get_x())
+        .collect();
+    let expected = [-4.0, 16.0, 98.0, 118.0];
+
+    for (position, expected) in positions.iter().zip(expected) {
+        assert!((position - expected).abs() < 0.001);
+    }
+}
+
+#[test]
+fn solver_keeps_massless_colliders_finite_test() {
+    let mut collidables = vec![
+        create_enemy_with(circle_at(0.0).with_mass(0.0)),
+        create_enemy_with(circle_at(12.0).with_mass(f32::NAN)),
+    ];
+    let mut symulation = WordSymulation::new();
+    solve(&mut symulation, &mut collidables);
+
+    for collidable in &collidables {
+        assert!(collidable.collider.position.get_x().is_finite());
+    }
+    assert!(
+        collidables[1].collider.position.get_x() - collidables[0].collider.position.get_x()
+            >= 19.99
+    );
+}
+
+#[test]
+fn solver_moves_only_lower_priority_side_test() {
+    let mut collidables = vec![
+        TestCollidable::new(
+            circle_at(0.0).with_pushback_priority(1),
+            CollisionMask::Player,
+        ),
+        create_enemy_with(circle_at(12.0).with_mass(100.0)),
+        create_enemy_with(circle_at(100.0).with_mass(f32::INFINITY)),
+        create_enemy_with(circle_at(112.0).with_mass(f32::INFINITY)),
+    ];
+    let mut symulation = WordSymulation::new();
+    solve(&mut symulation, &mut collidables);
+
+    assert_eq!(collidables[0].collider.position.get_x(), 0.0);
+    assert!((collidables[1].collider.position.get_x() - 20.0).abs() < 0.001);
+    assert_eq!(collidables[2].collider.position.get_x(), 100.0);
+    assert_eq!(collidables[3].collider.position.get_x(), 112.0);
+}
+
+#[test]
+fn solver_iterations_settle_crowd_test() {
+    let largest_overlap = |iterations: usize| {
+        //a row of circles squeezed against an immovable one
+        let mut collidables: Vec<TestCollidable> = (0..4)
+            .map(|i| create_enemy_with(circle_at(i as f32 * 15.0)))
+            .collect();
+        collidables[0].collider = collidables[0].collider.with_mass(f32::INFINITY);
+
+        let mut symulation = WordSymulation::new();
+        symulation.solver_iterations = iterations;
+        solve(&mut symulation, &mut collidables);
+
+        collidables
+            .windows(2)
+            .map(|pair| {
+                let distance =
+                    pair[1].collider.position.get_x() - pair[0].collider.position.get_x();
+                f32::max(0.0, 20.0 - distance)
+            })
+            .fold(0.0, f32::max)
+    };
+
+    assert!(largest_overlap(1) > largest_overlap(4));
+    assert!(largest_overlap(4) > largest_overlap(16));
+    assert!(largest_overlap(16) < 0.5);
+}
+
+#[test]
+fn occupied_cells_cover_inserted_boxes_test() {
+    let mut spatial_hash = SpatialHash::new(100.0);
+    spatial_hash.insert(
+        0,
+        BoundingBox::new(Position::new(90.0, -10.0), Position::new(110.0, 10.0)),
+    );
+
+    let mut cells: Vec<(f32, f32)> = spatial_hash
+        .get_occupied_cells()
+        .iter()
+        .map(|cell| (cell.min.get_x(), cell.min.get_y()))
+        .collect();
+    cells.sort_by(|first, second| first.partial_cmp(second).unwrap());
+
+    assert_eq!(
+        cells,
+        vec![(0.0, -100.0), (0.0, 0.0), (100.0, -100.0), (100.0, 0.0)]
+    );
+
+    for cell in spatial_hash.get_occupied_cells() {
+        assert_eq!(cell.max.get_x() - cell.min.get_x(), 100.0);
+        assert_eq!(cell.max.get_y() - cell.min.get_y(), 100.0);
+    }
+}
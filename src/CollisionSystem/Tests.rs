@@ -1,32 +1,82 @@
 use super::*;
 
+const EPSILON: f32 = 0.0001;
+
+fn assert_vector_eq(actual: Vector, expected: Vector) {
+    assert!(
+        (actual.get_x() - expected.get_x()).abs() < EPSILON
+            && (actual.get_y() - expected.get_y()).abs() < EPSILON,
+        "expected ({}, {}), got ({}, {})",
+        expected.get_x(),
+        expected.get_y(),
+        actual.get_x(),
+        actual.get_y()
+    );
+}
+
+fn assert_collision(info: Option<CollisionInfo>, normal: Vector, depth: f32, contact_point: Point) {
+    let info = info.expect("expected a collision");
+    assert_vector_eq(info.normal, normal);
+    assert!(
+        (info.depth - depth).abs() < EPSILON,
+        "expected depth {}, got {}",
+        depth,
+        info.depth
+    );
+    assert_vector_eq(info.contact_point, contact_point);
+}
+
 #[test]
 fn rectangle_rectangle_collision_tests() {
     let size_for_both_colliders = 1.0f32;
 
-    let first_collider = Collider {
-        position: Vector::default(),
-        shape: CollisionShape::Rectangle(Rectangle::new(
+    let first_collider = Collider::new(
+        CollisionShape::Rectangle(Rectangle::new(
             size_for_both_colliders,
             size_for_both_colliders,
         )),
-    };
+        Vector::default(),
+    );
 
-    let mut second_collider = Collider {
-        position: Vector::default(),
-        shape: CollisionShape::Rectangle(Rectangle::new(
+    let mut second_collider = Collider::new(
+        CollisionShape::Rectangle(Rectangle::new(
             size_for_both_colliders,
             size_for_both_colliders,
         )),
-    };
+        Vector::default(),
+    );
 
     assert!(Collider::collide(first_collider, second_collider).is_some());
+    assert_collision(
+        Collider::collide(first_collider, second_collider),
+        Vector::new(0.0, 1.0),
+        1.0,
+        Vector::new(0.0, 0.0),
+    );
 
     second_collider.position = Vector::new(-0.5, 0.0);
     assert!(Collider::collide(first_collider, second_collider).is_some());
+    assert_collision(
+        Collider::collide(first_collider, second_collider),
+        Vector::new(-1.0, 0.0),
+        0.5,
+        Vector::new(-0.25, 0.0),
+    );
+    assert_collision(
+        Collider::collide(second_collider, first_collider),
+        Vector::new(1.0, 0.0),
+        0.5,
+        Vector::new(-0.25, 0.0),
+    );
 
     second_collider.position = Vector::new(-0.5, 0.5);
     assert!(Collider::collide(first_collider, second_collider).is_some());
+    assert_collision(
+        Collider::collide(first_collider, second_collider),
+        Vector::new(0.0, 1.0),
+        0.5,
+        Vector::new(-0.25, 0.25),
+    );
 
     second_collider.position = Vector::new(-1.0, 0.0);
     assert!(Collider::collide(first_collider, second_collider).is_none());
@@ -39,24 +89,38 @@ fn rectangle_rectangle_collision_tests() {
 
     second_collider.position = Vector::new(0.99, 0.99);
     assert!(Collider::collide(first_collider, second_collider).is_some());
+    assert_collision(
+        Collider::collide(first_collider, second_collider),
+        Vector::new(0.0, 1.0),
+        0.01,
+        Vector::new(0.495, 0.495),
+    );
+
+    second_collider.position = Vector::new(0.2, -0.9);
+    assert_collision(
+        Collider::collide(first_collider, second_collider),
+        Vector::new(0.0, -1.0),
+        0.1,
+        Vector::new(0.1, -0.45),
+    );
 }
 
 #[test]
 fn circle_rectangle_collision_tests() {
     let size_for_both_colliders = 1.0f32;
 
-    let rectangle_collider = Collider {
-        position: Vector::default(),
-        shape: CollisionShape::Rectangle(Rectangle::new(
+    let rectangle_collider = Collider::new(
+        CollisionShape::Rectangle(Rectangle::new(
             size_for_both_colliders,
             size_for_both_colliders,
         )),
-    };
+        Vector::default(),
+    );
 
-    let mut circular_collider = Collider {
-        position: Vector::default(),
-        shape: CollisionShape::Circle(Circle::new(size_for_both_colliders)),
-    };
+    let mut circular_collider = Collider::new(
+        CollisionShape::Circle(Circle::new(size_for_both_colliders)),
+        Vector::default(),
+    );
 
     assert!(Collider::collide(rectangle_collider, circular_collider).is_some());
 
@@ -78,6 +142,40 @@ fn circle_rectangle_collision_tests() {
     circular_collider.position = Vector::new(0.5, -2.0);
     assert!(Collider::collide(rectangle_collider, circular_collider).is_none());
 
+    //circle centre outside of the rectangle, pushed along the line to the nearest point
+    circular_collider.position = Vector::new(0.5, -0.99);
+    assert_collision(
+        Collider::collide(rectangle_collider, circular_collider),
+        Vector::new(0.0, -1.0),
+        0.51,
+        Vector::new(0.5, -0.5),
+    );
+
+    circular_collider.position = Vector::new(1.1, 0.9);
+    assert_collision(
+        Collider::collide(rectangle_collider, circular_collider),
+        Vector::new(0.6, 0.4).normal().unwrap(),
+        1.0 - Vector::new(0.6, 0.4).length(),
+        Vector::new(0.5, 0.5),
+    );
+
+    //circle centre inside of the rectangle, pushed out through the nearest edge
+    circular_collider.position = Vector::new(0.5, 0.0);
+    assert_collision(
+        Collider::collide(rectangle_collider, circular_collider),
+        Vector::new(1.0, 0.0),
+        1.0,
+        Vector::new(0.5, 0.0),
+    );
+
+    circular_collider.position = Vector::new(-0.1, 0.3);
+    assert_collision(
+        Collider::collide(rectangle_collider, circular_collider),
+        Vector::new(0.0, 1.0),
+        1.2,
+        Vector::new(-0.1, 0.5),
+    );
+
     //symetric calls
     circular_collider.position = Vector::new(-0.5, -0.5);
     assert!(Collider::collide(circular_collider, rectangle_collider).is_some());
@@ -96,26 +194,390 @@ fn circle_rectangle_collision_tests() {
 
     circular_collider.position = Vector::new(0.5, -2.0);
     assert!(Collider::collide(circular_collider, rectangle_collider).is_none());
+
+    circular_collider.position = Vector::new(0.5, -0.99);
+    assert_collision(
+        Collider::collide(circular_collider, rectangle_collider),
+        Vector::new(0.0, 1.0),
+        0.51,
+        Vector::new(0.5, -0.5),
+    );
+
+    circular_collider.position = Vector::new(0.5, 0.0);
+    assert_collision(
+        Collider::collide(circular_collider, rectangle_collider),
+        Vector::new(-1.0, 0.0),
+        1.0,
+        Vector::new(0.5, 0.0),
+    );
 }
 
 #[test]
 fn circle_to_circle_collision_test() {
     let radius = 1.0f32;
-    let first_collider = Collider {
-        position: Vector::default(),
-        shape: CollisionShape::Circle(Circle::new(radius)),
-    };
+    let first_collider = Collider::new(
+        CollisionShape::Circle(Circle::new(radius)),
+        Vector::default(),
+    );
 
-    let mut second_collider = Collider {
-        position: Vector::default(),
-        shape: CollisionShape::Circle(Circle::new(radius)),
-    };
+    let mut second_collider = Collider::new(
+        CollisionShape::Circle(Circle::new(radius)),
+        Vector::default(),
+    );
 
     assert!(Collider::collide(first_collider, second_collider).is_some());
+    assert_collision(
+        Collider::collide(first_collider, second_collider),
+        Vector::new(1.0, 0.0),
+        2.0,
+        Vector::new(0.0, 0.0),
+    );
 
     second_collider.position = Vector::new(2.0, 0.0);
     assert!(Collider::collide(first_collider, second_collider).is_none());
 
     second_collider.position = Vector::new(1.99, 0.0);
     assert!(Collider::collide(first_collider, second_collider).is_some());
+    assert_collision(
+        Collider::collide(first_collider, second_collider),
+        Vector::new(1.0, 0.0),
+        0.01,
+        Vector::new(0.995, 0.0),
+    );
+
+    second_collider.position = Vector::new(0.0, -1.5);
+    assert_collision(
+        Collider::collide(first_collider, second_collider),
+        Vector::new(0.0, -1.0),
+        0.5,
+        Vector::new(0.0, -0.75),
+    );
+    assert_collision(
+        Collider::collide(second_collider, first_collider),
+        Vector::new(0.0, 1.0),
+        0.5,
+        Vector::new(0.0, -0.75),
+    );
+}
+
+#[test]
+fn swept_circle_to_rectangle_test() {
+    let rectangle_collider = Collider::new(
+        CollisionShape::Rectangle(Rectangle::new(40.0, 40.0)),
+        Vector::default(),
+    );
+    let radius = 5.0f32;
+    let motion = Vector::new(200.0, 0.0);
+
+    let sweep_info = Collider::swept_circle_collision(
+        Vector::new(-100.0, 0.0),
+        radius,
+        motion,
+        rectangle_collider,
+    )
+    .unwrap();
+    assert!((sweep_info.time_of_impact - 0.375).abs() < 0.0001);
+    assert_eq!(sweep_info.normal.get_x(), -1.0);
+    assert_eq!(sweep_info.normal.get_y(), 0.0);
+
+    //passes close to the corner, hits the rounded part of the swept shape
+    let sweep_info = Collider::swept_circle_collision(
+        Vector::new(-100.0, 24.0),
+        radius,
+        motion,
+        rectangle_collider,
+    )
+    .unwrap();
+    assert!((sweep_info.time_of_impact - 0.385).abs() < 0.0001);
+
+    assert!(Collider::swept_circle_collision(
+        Vector::new(-100.0, 26.0),
+        radius,
+        motion,
+        rectangle_collider
+    )
+    .is_none());
+
+    assert!(Collider::swept_circle_collision(
+        Vector::new(-100.0, 0.0),
+        radius,
+        motion * 0.3,
+        rectangle_collider
+    )
+    .is_none());
+}
+
+#[test]
+fn swept_circle_to_circle_test() {
+    let circular_collider =
+        Collider::new(CollisionShape::Circle(Circle::new(20.0)), Vector::default());
+    let motion = Vector::new(200.0, 0.0);
+
+    let sweep_info =
+        Collider::swept_circle_collision(Vector::new(-100.0, 0.0), 5.0, motion, circular_collider)
+            .unwrap();
+    assert!((sweep_info.time_of_impact - 0.375).abs() < 0.0001);
+    assert_eq!(sweep_info.normal.get_x(), -1.0);
+
+    assert!(Collider::swept_circle_collision(
+        Vector::new(-100.0, 25.5),
+        5.0,
+        motion,
+        circular_collider
+    )
+    .is_none());
+}
+
+#[test]
+fn fast_circle_does_not_tunnel_through_rectangle_test() {
+    let rectangle_collider = Collider::new(
+        CollisionShape::Rectangle(Rectangle::new(40.0, 40.0)),
+        Vector::default(),
+    );
+
+    //ends the frame behind the rectangle without overlapping it
+    let missile_collider = Collider::new(
+        CollisionShape::Circle(Circle::new(5.0)),
+        Vector::new(100.0, 0.0),
+    );
+    assert!(Collider::collide(missile_collider, rectangle_collider).is_none());
+
+    let missile_collider = missile_collider.with_motion(Vector::new(200.0, 0.0));
+    let info = Collider::collide(missile_collider, rectangle_collider).unwrap();
+    assert!((info.time_of_impact.unwrap() - 0.375).abs() < 0.0001);
+    assert_vector_eq(info.normal, Vector::new(1.0, 0.0));
+    assert_vector_eq(info.contact_point, Vector::new(-20.0, 0.0));
+
+    let info = Collider::collide(rectangle_collider, missile_collider).unwrap();
+    assert!((info.time_of_impact.unwrap() - 0.375).abs() < 0.0001);
+    assert_vector_eq(info.normal, Vector::new(-1.0, 0.0));
+    assert_vector_eq(info.contact_point, Vector::new(-20.0, 0.0));
+}
+
+#[test]
+fn oriented_rectangle_collision_test() {
+    let square = OrientedRectangle::new(2.0, 2.0);
+    let circular_collider = Collider::new(
+        CollisionShape::Circle(Circle::new(0.5)),
+        Vector::new(1.5, 0.0),
+    );
+
+    let axis_aligned_square =
+        Collider::new(CollisionShape::OrientedRectangle(square), Vector::default());
+    assert!(Collider::collide(axis_aligned_square, circular_collider).is_none());
+
+    //turned by 45 degrees the corner reaches x = sqrt(2)
+    let turned_square = axis_aligned_square.with_rotation(45.0);
+    assert_collision(
+        Collider::collide(turned_square, circular_collider),
+        Vector::new(1.0, 0.0),
+        0.5 - (1.5 - f32::sqrt(2.0)),
+        Vector::new((f32::sqrt(2.0) + 1.0) * 0.5, 0.0),
+    );
+    assert_collision(
+        Collider::collide(circular_collider, turned_square),
+        Vector::new(-1.0, 0.0),
+        0.5 - (1.5 - f32::sqrt(2.0)),
+        Vector::new((f32::sqrt(2.0) + 1.0) * 0.5, 0.0),
+    );
+
+    let bounding_box = turned_square.bounding_box();
+    assert_vector_eq(
+        bounding_box.min,
+        Vector::new(-f32::sqrt(2.0), -f32::sqrt(2.0)),
+    );
+    assert_vector_eq(
+        bounding_box.max,
+        Vector::new(f32::sqrt(2.0), f32::sqrt(2.0)),
+    );
+
+    let mut other_square = Collider::new(
+        CollisionShape::OrientedRectangle(square),
+        Vector::new(1.9, 0.0),
+    );
+    assert_collision(
+        Collider::collide(axis_aligned_square, other_square),
+        Vector::new(1.0, 0.0),
+        0.1,
+        Vector::new(0.95, 0.0),
+    );
+
+    other_square.position = Vector::new(2.9, 0.0);
+    assert!(Collider::collide(turned_square, other_square.with_rotation(45.0)).is_none());
+}
+
+#[test]
+fn capsule_collision_test() {
+    let capsule = Collider::new(
+        CollisionShape::Capsule(Capsule::new(4.0, 0.5)),
+        Vector::default(),
+    );
+    let circular_collider = Collider::new(
+        CollisionShape::Circle(Circle::new(0.5)),
+        Vector::new(0.8, 1.5),
+    );
+
+    assert!(Collider::collide(capsule, circular_collider).is_none());
+
+    let vertical_capsule = capsule.with_rotation(90.0);
+    assert_collision(
+        Collider::collide(vertical_capsule, circular_collider),
+        Vector::new(1.0, 0.0),
+        0.2,
+        Vector::new(0.4, 1.5),
+    );
+
+    //round ends, the same circle misses a box of the same size
+    let end_circle = Collider::new(
+        CollisionShape::Circle(Circle::new(0.5)),
+        Vector::new(2.75, 0.75),
+    );
+    assert!(Collider::collide(capsule, end_circle).is_none());
+    let box_collider = Collider::new(
+        CollisionShape::OrientedRectangle(OrientedRectangle::new(5.0, 1.0)),
+        Vector::default(),
+    );
+    assert!(Collider::collide(box_collider, end_circle).is_some());
+
+    //crossing capsules have to be pushed past the end of one of the segments
+    let thin_capsule = Collider::new(
+        CollisionShape::Capsule(Capsule::new(4.0, 0.25)),
+        Vector::default(),
+    );
+    let info = Collider::collide(thin_capsule, thin_capsule.with_rotation(90.0)).unwrap();
+    assert!((info.depth - 2.5).abs() < EPSILON);
+}
+
+#[test]
+fn contained_shape_collision_test() {
+    let big_box = Collider::new(
+        CollisionShape::OrientedRectangle(OrientedRectangle::new(10.0, 10.0)),
+        Vector::default(),
+    );
+    let small_box = Collider::new(
+        CollisionShape::OrientedRectangle(OrientedRectangle::new(2.0, 2.0)),
+        Vector::new(3.0, 0.5),
+    );
+
+    //pushed out through the closest side, not by the size of the smaller shape
+    let info = Collider::collide(big_box, small_box).unwrap();
+    assert_vector_eq(info.normal, Vector::new(1.0, 0.0));
+    assert!((info.depth - 3.0).abs() < EPSILON);
+
+    let point = Collider::new(
+        CollisionShape::Circle(Circle::new(0.0)),
+        Vector::new(-4.0, 1.0),
+    );
+    let info = Collider::collide(big_box, point).unwrap();
+    assert_vector_eq(info.normal, Vector::new(-1.0, 0.0));
+    assert!((info.depth - 1.0).abs() < EPSILON);
+}
+
+#[test]
+fn convex_polygon_rejects_broken_vertices_test() {
+    let square = [
+        Vector::new(0.0, 0.0),
+        Vector::new(1.0, 0.0),
+        Vector::new(1.0, 1.0),
+        Vector::new(0.0, 1.0),
+    ];
+    assert!(ConvexPolygon::try_new(&square).is_ok());
+    //the other way around is as good
+    let reversed: Vec<Vector> = square.iter().rev().copied().collect();
+    assert!(ConvexPolygon::try_new(&reversed).is_ok());
+
+    let too_many: Vec<Vector> = (0..MAX_POLYGON_VERTICES + 1)
+        .map(|index| Vector::new(1.0, 0.0).rotated(index as f32 * 30.0))
+        .collect();
+    assert!(ConvexPolygon::try_new(&too_many).is_err());
+    assert!(ConvexPolygon::try_new(&square[..2]).is_err());
+
+    let concave = [
+        Vector::new(0.0, 0.0),
+        Vector::new(2.0, 0.0),
+        Vector::new(0.5, 0.5),
+        Vector::new(0.0, 2.0),
+    ];
+    assert!(ConvexPolygon::try_new(&concave).is_err());
+
+    let flat = [
+        Vector::new(0.0, 0.0),
+        Vector::new(1.0, 0.0),
+        Vector::new(2.0, 0.0),
+        Vector::new(1.0, 1.0),
+    ];
+    assert!(ConvexPolygon::try_new(&flat).is_err());
+
+    //every corner of a star turns the same way, but it goes around twice
+    let star: Vec<Vector> = (0..5)
+        .map(|index| Vector::new(1.0, 0.0).rotated(index as f32 * 144.0))
+        .collect();
+    assert!(ConvexPolygon::try_new(&star).is_err());
+}
+
+#[test]
+fn convex_polygon_collision_test() {
+    let triangle = ConvexPolygon::new(&[
+        Vector::new(0.0, -1.0),
+        Vector::new(1.0, 1.0),
+        Vector::new(-1.0, 1.0),
+    ]);
+    let triangle_collider =
+        Collider::new(CollisionShape::ConvexPolygon(triangle), Vector::default());
+    let mut rectangle_collider = Collider::new(
+        CollisionShape::Rectangle(Rectangle::new(2.0, 2.0)),
+        Vector::new(0.0, 2.5),
+    );
+
+    assert!(Collider::collide(triangle_collider, rectangle_collider).is_none());
+
+    rectangle_collider.position = Vector::new(0.0, 1.9);
+    assert_collision(
+        Collider::collide(triangle_collider, rectangle_collider),
+        Vector::new(0.0, 1.0),
+        0.1,
+        Vector::new(0.0, 0.95),
+    );
+    assert_collision(
+        Collider::collide(rectangle_collider, triangle_collider),
+        Vector::new(0.0, -1.0),
+        0.1,
+        Vector::new(0.0, 0.95),
+    );
+
+    //upside down the tip points at the rectangle and stays out of it
+    let turned_triangle = triangle_collider.with_rotation(180.0);
+    rectangle_collider.position = Vector::new(0.0, 2.05);
+    assert!(Collider::collide(turned_triangle, rectangle_collider).is_none());
+
+    rectangle_collider.position = Vector::new(0.0, 1.9);
+    let info = Collider::collide(turned_triangle, rectangle_collider).unwrap();
+    assert_vector_eq(info.normal, Vector::new(0.0, 1.0));
+    assert!((info.depth - 0.1).abs() < EPSILON);
+}
+
+#[test]
+fn swept_circle_to_rotated_shape_test() {
+    let turned_square = Collider::new(
+        CollisionShape::OrientedRectangle(OrientedRectangle::new(40.0, 40.0)),
+        Vector::default(),
+    )
+    .with_rotation(45.0);
+
+    let missile_collider = Collider::new(
+        CollisionShape::Circle(Circle::new(5.0)),
+        Vector::new(100.0, 0.0),
+    );
+    assert!(Collider::collide(missile_collider, turned_square).is_none());
+
+    let info = Collider::collide(
+        missile_collider.with_motion(Vector::new(200.0, 0.0)),
+        turned_square,
+    )
+    .unwrap();
+    let time_of_impact = info.time_of_impact.unwrap();
+    //the corner is at x = -20 * sqrt(2), the circle touches it 5 units earlier
+    let expected_time_of_impact = (100.0 - 20.0 * f32::sqrt(2.0) - 5.0) / 200.0;
+    assert!(time_of_impact >= expected_time_of_impact);
+    assert!(time_of_impact - expected_time_of_impact < 5.0 / 200.0);
 }
@@ -1,66 +1,295 @@
+#[cfg(test)]
 mod Tests;
 
-use sfml::graphics::RenderWindow;
+use sfml::window::Event;
 
-use crate::{Objects::Interfaces::{Updatable, Drawable}, InputSystem::{InputConsumer, Input}};
+use crate::{Objects::Interfaces::{Updatable, Drawable, Renderer}, InputSystem::{InputConsumer, Input, InputContext}};
 
 pub mod Interfaces;
 use self::Interfaces::State;
 
-#[derive(PartialEq)]
+pub mod TransitionEffects;
+use self::TransitionEffects::TransitionEffect;
+
+#[derive(PartialEq, Debug)]
 pub enum FlowState{
+    /// Only the first state waits for an update to be entered, the others are entered when
+    /// the transition to them starts.
     Enter,
     Update,
     Exit,
+    /// The stack already changed and the effect is playing, nothing is updated or gets input.
+    Transitioning,
+}
+
+/// What happens to the stack of states when the state on top exits.
+pub enum Transition<StatesEnum>{
+    /// The new state goes on top, the exiting one waits below it until it is popped.
+    Push(StatesEnum),
+    /// Popping the last state quits.
+    Pop,
+    Replace(StatesEnum),
+    /// Every state on the stack gets `onQuit` and the state machine stops.
+    Quit,
+}
+
+/// Transition being animated, the states before it are the first `before_count` states of the
+/// stack, with the state which left the stack on top of them if there is one.
+struct ActiveTransition<StatesEnum> {
+    effect: TransitionEffect,
+    elapsed_time: f32,
+    before_count: usize,
+    outgoing_state: Option<StatesEnum>,
+    flow_state_after: FlowState,
 }
 
-pub struct StateMachine<StatesEnum: Default + State<StatesEnum> + Drawable + InputConsumer> {
-    current_state: StatesEnum,
+/// Pushdown automaton, only the state on top is updated and gets input.
+pub struct StateMachine<StatesEnum: State<StatesEnum> + Drawable + InputConsumer> {
+    states: Vec<StatesEnum>,
     flow_state: FlowState,
+    has_quit: bool,
+
+    active_transition: Option<ActiveTransition<StatesEnum>>,
 }
 
 impl<StatesEnum: Default + State<StatesEnum> + Drawable + InputConsumer> StateMachine<StatesEnum> {
+    //the program's states need what it shares with them, so they are built with `with_state`
+    #[cfg(test)]
     pub fn new() -> Self {
+        Self::with_state(StatesEnum::default())
+    }
+}
+
+impl<StatesEnum: State<StatesEnum> + Drawable + InputConsumer> StateMachine<StatesEnum> {
+    pub fn with_state(state: StatesEnum) -> Self {
         Self {
-            current_state: StatesEnum::default(),
+            states: vec![state],
             flow_state: FlowState::Enter,
+            has_quit: false,
+            active_transition: None,
+        }
+    }
+
+    pub fn get_current_state(&self) -> &StatesEnum {
+        self.states.last().unwrap()
+    }
+
+    pub fn get_current_state_mut(&mut self) -> &mut StatesEnum {
+        self.states.last_mut().unwrap()
+    }
+
+    /// From the bottom of the stack to its top.
+    #[cfg(test)]
+    pub fn get_states(&self) -> &[StatesEnum] {
+        &self.states
+    }
+
+    /// Runs the quit hooks of the states, from the top of the stack to its bottom. Does nothing
+    /// once the state machine has quit.
+    pub fn quit(&mut self) {
+        if self.has_quit {
+            return;
+        }
+
+        for state in self.states.iter_mut().rev() {
+            state.onQuit();
+        }
+        self.has_quit = true;
+    }
+
+    pub fn has_quit(&self) -> bool {
+        self.has_quit
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.flow_state == FlowState::Transitioning
+    }
+
+    /// From 0 when the transition starts to 1 when it ends, 1 outside of transitions.
+    pub fn get_transition_progress(&self) -> f32 {
+        match &self.active_transition {
+            Some(transition) => (transition.elapsed_time / transition.effect.get_duration()).min(1.0),
+            None => 1.0,
+        }
+    }
+
+    fn exit_current_state(&mut self) -> FlowState {
+        //asked before onExit, which may reset what the state would choose
+        let effect = self.get_current_state().get_transition_effect();
+        let transition = self.get_current_state_mut().onExit();
+        let mut before_count = self.states.len() - 1;
+
+        //the new state is entered right away, the transition already draws it
+        let (outgoing_state, flow_state_after) = match transition {
+            Transition::Push(state) => {
+                before_count += 1;
+                self.states.push(state);
+                self.get_current_state_mut().onEnter();
+                (None, FlowState::Update)
+            }
+            Transition::Replace(state) => {
+                let outgoing_state = std::mem::replace(self.get_current_state_mut(), state);
+                self.get_current_state_mut().onEnter();
+                (Some(outgoing_state), FlowState::Update)
+            }
+            Transition::Pop if self.states.len() == 1 => {
+                self.quit();
+                return FlowState::Update;
+            }
+            Transition::Pop => {
+                let outgoing_state = self.states.pop();
+                self.get_current_state_mut().onResume();
+                (outgoing_state, FlowState::Update)
+            }
+            Transition::Quit => {
+                self.quit();
+                return FlowState::Update;
+            }
+        };
+
+        if effect.get_duration() <= 0.0 {
+            return flow_state_after;
+        }
+
+        self.active_transition = Some(ActiveTransition {
+            effect,
+            elapsed_time: 0.0,
+            before_count,
+            outgoing_state,
+            flow_state_after,
+        });
+        FlowState::Transitioning
+    }
+
+    fn update_transition(&mut self, delta_time: f32) -> FlowState {
+        let transition = self.active_transition.as_mut().unwrap();
+        transition.elapsed_time += delta_time;
+        if transition.elapsed_time < transition.effect.get_duration() {
+            return FlowState::Transitioning;
+        }
+
+        self.active_transition.take().unwrap().flow_state_after
+    }
+
+    /// Overlays on top are drawn over the first state below them which isn't one.
+    #[cfg(test)]
+    fn get_first_drawn_state_index(&self) -> usize {
+        Self::get_first_drawn_index(self.states.iter())
+    }
+
+    fn get_first_drawn_index<'a, StatesIterator>(mut states: StatesIterator) -> usize
+    where
+        StatesEnum: 'a,
+        StatesIterator: DoubleEndedIterator<Item = &'a StatesEnum> + ExactSizeIterator,
+    {
+        states.rposition(|state| !state.is_overlay()).unwrap_or(0)
+    }
+
+    fn draw_states(states: Vec<&mut StatesEnum>, target: &mut dyn Renderer, alpha: f32) {
+        let first_drawn_state_index = Self::get_first_drawn_index(states.iter().map(|state| &**state));
+        for state in states.into_iter().skip(first_drawn_state_index) {
+            state.draw(target, alpha);
+        }
+    }
+
+    fn draw_states_before_transition(&mut self, target: &mut dyn Renderer, alpha: f32) {
+        let transition = self.active_transition.as_mut().unwrap();
+        let states_before = self.states[..transition.before_count]
+            .iter_mut()
+            .chain(transition.outgoing_state.iter_mut())
+            .collect();
+        Self::draw_states(states_before, target, alpha);
+    }
+
+    fn draw_states_after_transition(&mut self, target: &mut dyn Renderer, alpha: f32) {
+        Self::draw_states(self.states.iter_mut().collect(), target, alpha);
+    }
+
+    fn draw_transition(&mut self, target: &mut dyn Renderer, alpha: f32) {
+        let progress = self.get_transition_progress();
+
+        match self.active_transition.as_ref().unwrap().effect {
+            TransitionEffect::Cut => self.draw_states_after_transition(target, alpha),
+            TransitionEffect::FadeToBlack { .. } => {
+                if progress < 0.5 {
+                    self.draw_states_before_transition(target, alpha);
+                    TransitionEffects::draw_black_overlay(target, progress * 2.0);
+                } else {
+                    self.draw_states_after_transition(target, alpha);
+                    TransitionEffects::draw_black_overlay(target, (1.0 - progress) * 2.0);
+                }
+            }
+            TransitionEffect::CrossFade { .. } => {
+                self.draw_states_before_transition(target, alpha);
+                target.draw_faded(progress, &mut |target| self.draw_states_after_transition(target, alpha));
+            }
+            TransitionEffect::Slide { direction, .. } => {
+                let previous_offset = target.get_offset();
+
+                let before_offset = TransitionEffects::get_slide_offset(target, direction, progress);
+                target.set_offset(previous_offset + before_offset);
+                self.draw_states_before_transition(target, alpha);
+
+                let after_offset = TransitionEffects::get_slide_offset(target, direction, progress - 1.0);
+                target.set_offset(previous_offset + after_offset);
+                self.draw_states_after_transition(target, alpha);
+
+                target.set_offset(previous_offset);
+            }
         }
     }
 }
 
-impl<StatesEnum: Default + State<StatesEnum> + Drawable + InputConsumer> Updatable for StateMachine<StatesEnum> {
+impl<StatesEnum: State<StatesEnum> + Drawable + InputConsumer> Updatable for StateMachine<StatesEnum> {
     fn update(&mut self, delta_time: f32) {
+       if self.has_quit {
+           return;
+       }
+
        self.flow_state = match self.flow_state{
             FlowState::Enter => {
-                self.current_state.onEnter();
+                self.get_current_state_mut().onEnter();
                 FlowState::Update
             }
             FlowState::Update => {
-                self.current_state.onUpdate(delta_time)
+                self.get_current_state_mut().onUpdate(delta_time)
             },
             FlowState::Exit => {
-                let new_state = self.current_state.onExit();
-                
-                match new_state {
-                    Some(state) => {
-                        self.current_state = state;    
-                        FlowState::Enter
-                    }
-                    None => panic!(),
-                }
+                self.exit_current_state()
+            },
+            FlowState::Transitioning => {
+                self.update_transition(delta_time)
             },
         }
     }
 }
 
-impl<StatesEnum: Default + State<StatesEnum> + Drawable + InputConsumer> Drawable for StateMachine<StatesEnum>{
-    fn draw(&mut self, window: &mut RenderWindow) {
-        self.current_state.draw(window);
+impl<StatesEnum: State<StatesEnum> + Drawable + InputConsumer> Drawable for StateMachine<StatesEnum>{
+    fn draw(&mut self, renderer: &mut dyn Renderer, alpha: f32) {
+        if self.active_transition.is_some() {
+            self.draw_transition(renderer, alpha);
+        } else {
+            self.draw_states_after_transition(renderer, alpha);
+        }
     }
 }
 
-impl <StatesEnum: Default + State<StatesEnum> + Drawable + InputConsumer> InputConsumer for StateMachine<StatesEnum>{
+impl <StatesEnum: State<StatesEnum> + Drawable + InputConsumer> InputConsumer for StateMachine<StatesEnum>{
     fn handle_input(&mut self, input: &Input) {
-        self.current_state.handle_input(input);
+        if self.is_transitioning() {
+            return;
+        }
+        self.get_current_state_mut().handle_input(input);
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        if self.is_transitioning() {
+            return;
+        }
+        self.get_current_state_mut().on_event(event);
+    }
+
+    fn get_input_context(&self) -> InputContext {
+        self.get_current_state().get_input_context()
     }
 }
\ No newline at end of file
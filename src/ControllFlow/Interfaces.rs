@@ -1,7 +1,23 @@
-use super::FlowState;
+use super::{FlowState, Transition, TransitionEffects::TransitionEffect};
 
 pub trait State<StatesEnum> {
     fn onEnter(&mut self);
     fn onUpdate(&mut self, delta_time: f32)->FlowState;
-    fn onExit(&mut self) -> Option<StatesEnum>;
+    fn onExit(&mut self) -> Transition<StatesEnum>;
+
+    /// Called when the state above this one is popped and this one is on top again.
+    fn onResume(&mut self) {}
+
+    /// Called on every state on the stack before the program closes, for things like saving.
+    fn onQuit(&mut self) {}
+
+    /// Effect of the transition the state is about to exit with, asked right before `onExit`.
+    fn get_transition_effect(&self) -> TransitionEffect {
+        TransitionEffect::Cut
+    }
+
+    /// Overlays are drawn over the state below them instead of replacing it on screen.
+    fn is_overlay(&self) -> bool {
+        false
+    }
 }
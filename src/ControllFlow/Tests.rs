@@ -1,24 +1,229 @@
-use crate::Objects::Interfaces::Updatable;
+use crate::InputSystem::{Input, InputConsumer};
+use crate::MathUtilities::Vector;
+use crate::Objects::Interfaces::{Drawable, Updatable};
+use crate::Objects::Renderers::RecordingRenderer::{DrawCommand, RecordingRenderer};
+use sfml::graphics::Color;
 
-use super::FlowState;
-use super::State;
 use super::StateMachine;
+use super::TransitionEffects::TransitionEffect;
 
 mod TestInnerStates;
-use TestInnerStates::{TestState1InnerState, TestState2InnerState};
+use TestInnerStates::{TestStackInnerState, TestTransition};
 
 mod TestStateEnums;
 use TestStateEnums::TestStatesEnum;
 
 #[test]
-#[should_panic]
 fn general_state_switching_test() {
-    let s1 = Box::new(TestStatesEnum::TestState1(TestState1InnerState::default()));
-    let s2 = Box::new(TestStatesEnum::TestState2(TestState2InnerState::default()));
-
     let mut state_machine: StateMachine<TestStatesEnum> = StateMachine::new();
 
-    while true {
+    //the second state pops itself, which quits instead of leaving the stack empty
+    for _ in 0..100 {
+        state_machine.update(0.0);
+    }
+
+    assert!(state_machine.has_quit());
+}
+
+
+fn create_stack_state_machine() -> StateMachine<TestStatesEnum> {
+    let base = TestStatesEnum::Stack(TestStackInnerState::new("base", false));
+    let mut state_machine = StateMachine::with_state(base);
+    //enter and the first update
+    state_machine.update(0.0);
+    state_machine.update(0.0);
+    state_machine
+}
+
+//one update to return FlowState::Exit and one more to apply the transition
+fn transition(state_machine: &mut StateMachine<TestStatesEnum>, transition: TestTransition) {
+    state_machine.get_current_state_mut().get_stack_state_mut().next_transition = Some(transition);
+    state_machine.update(0.0);
+    state_machine.update(0.0);
+}
+
+fn get_stack_names(state_machine: &StateMachine<TestStatesEnum>) -> Vec<&'static str> {
+    state_machine.get_states().iter().map(|state| state.get_stack_state().name).collect()
+}
+
+#[test]
+fn push_keeps_state_below_without_updating_it_test() {
+    let mut state_machine = create_stack_state_machine();
+
+    transition(&mut state_machine, TestTransition::PushOverlay);
+    assert_eq!(get_stack_names(&state_machine), vec!["base", "overlay"]);
+
+    for _ in 0..4 {
         state_machine.update(0.0);
     }
+
+    let base = state_machine.get_states()[0].get_stack_state();
+    let overlay = state_machine.get_current_state().get_stack_state();
+    assert_eq!(base.update_count, 2);
+    assert_eq!(overlay.enter_count, 1);
+    assert_eq!(overlay.update_count, 4);
+
+    //the overlay is drawn over the base
+    assert_eq!(state_machine.get_first_drawn_state_index(), 0);
+}
+
+#[test]
+fn pop_resumes_state_below_test() {
+    let mut state_machine = create_stack_state_machine();
+    transition(&mut state_machine, TestTransition::PushOverlay);
+    state_machine.update(0.0);
+
+    transition(&mut state_machine, TestTransition::Pop);
+    assert_eq!(get_stack_names(&state_machine), vec!["base"]);
+
+    state_machine.update(0.0);
+    let base = state_machine.get_current_state().get_stack_state();
+    assert_eq!(base.resume_count, 1);
+    //resuming doesn't enter the state again
+    assert_eq!(base.enter_count, 1);
+    assert_eq!(base.update_count, 3);
+}
+
+#[test]
+fn replace_swaps_only_top_state_test() {
+    let mut state_machine = create_stack_state_machine();
+    transition(&mut state_machine, TestTransition::PushOverlay);
+    state_machine.update(0.0);
+
+    transition(&mut state_machine, TestTransition::Replace);
+    state_machine.update(0.0);
+
+    assert_eq!(get_stack_names(&state_machine), vec!["base", "replacement"]);
+    assert_eq!(state_machine.get_current_state().get_stack_state().enter_count, 1);
+    //the replacement isn't an overlay so it hides the base
+    assert_eq!(state_machine.get_first_drawn_state_index(), 1);
+}
+
+#[test]
+fn quit_runs_hooks_of_whole_stack_once_test() {
+    let mut state_machine = create_stack_state_machine();
+    transition(&mut state_machine, TestTransition::PushOverlay);
+    state_machine.update(0.0);
+
+    transition(&mut state_machine, TestTransition::Quit);
+    assert!(state_machine.has_quit());
+
+    //a quit state machine stays as it was
+    let overlay_update_count = state_machine.get_current_state().get_stack_state().update_count;
+    state_machine.update(0.0);
+    state_machine.quit();
+
+    let overlay = state_machine.get_current_state().get_stack_state();
+    assert_eq!(overlay.update_count, overlay_update_count);
+    for state in state_machine.get_states() {
+        assert_eq!(state.get_stack_state().quit_count, 1);
+    }
+}
+
+#[test]
+fn popping_last_state_quits_test() {
+    let mut state_machine = create_stack_state_machine();
+
+    transition(&mut state_machine, TestTransition::Pop);
+
+    assert!(state_machine.has_quit());
+    assert_eq!(get_stack_names(&state_machine), vec!["base"]);
+    assert_eq!(state_machine.get_current_state().get_stack_state().quit_count, 1);
+}
+
+#[test]
+fn timed_transition_blocks_input_until_finished_test() {
+    let mut state_machine = create_stack_state_machine();
+    let input = Input::new();
+    state_machine.get_current_state_mut().get_stack_state_mut().exit_effect =
+        Some(TransitionEffect::FadeToBlack { duration: 1.0 });
+
+    transition(&mut state_machine, TestTransition::PushOverlay);
+    assert!(state_machine.is_transitioning());
+    assert_eq!(get_stack_names(&state_machine), vec!["base", "overlay"]);
+
+    state_machine.handle_input(&input);
+    state_machine.update(0.4);
+    assert!((state_machine.get_transition_progress() - 0.4).abs() < 0.0001);
+    assert!(state_machine.is_transitioning());
+
+    state_machine.update(0.7);
+    assert!(!state_machine.is_transitioning());
+
+    //the pushed state is entered when the effect starts and updated once it is over
+    assert_eq!(state_machine.get_current_state().get_stack_state().update_count, 0);
+    state_machine.update(0.0);
+    state_machine.handle_input(&input);
+
+    let overlay = state_machine.get_current_state().get_stack_state();
+    assert_eq!(overlay.enter_count, 1);
+    assert_eq!(overlay.update_count, 1);
+    assert_eq!(overlay.input_count, 1);
+    assert_eq!(state_machine.get_states()[0].get_stack_state().input_count, 0);
+}
+
+#[test]
+fn cut_transition_is_immediate_test() {
+    let mut state_machine = create_stack_state_machine();
+
+    transition(&mut state_machine, TestTransition::Replace);
+
+    assert!(!state_machine.is_transitioning());
+    assert_eq!(state_machine.get_transition_progress(), 1.0);
+    assert_eq!(state_machine.get_current_state().get_stack_state().enter_count, 1);
+}
+
+#[test]
+fn states_are_entered_before_they_are_drawn_test() {
+    let mut renderer = RecordingRenderer::new(Vector::new(800.0, 600.0));
+    for effect in [TransitionEffect::Cut, TransitionEffect::CrossFade { duration: 1.0 }] {
+        let mut state_machine = create_stack_state_machine();
+        state_machine.get_current_state_mut().get_stack_state_mut().exit_effect = Some(effect);
+
+        transition(&mut state_machine, TestTransition::Replace);
+        state_machine.draw(&mut renderer, 1.0);
+        state_machine.update(1.0);
+        state_machine.draw(&mut renderer, 1.0);
+
+        let replacement = state_machine.get_current_state().get_stack_state();
+        assert_eq!(replacement.calls, vec!["enter", "draw", "draw"]);
+    }
+}
+
+#[test]
+fn fade_to_black_draws_overlay_over_states_test() {
+    let mut state_machine = create_stack_state_machine();
+    let mut renderer = RecordingRenderer::new(Vector::new(800.0, 600.0));
+    state_machine.get_current_state_mut().get_stack_state_mut().exit_effect =
+        Some(TransitionEffect::FadeToBlack { duration: 1.0 });
+
+    transition(&mut state_machine, TestTransition::PushOverlay);
+    state_machine.update(0.25);
+    state_machine.draw(&mut renderer, 1.0);
+
+    //a quarter of the way the old states are half faded out
+    let overlay = DrawCommand::Rectangle {
+        position: Vector::default(),
+        size: Vector::new(800.0, 600.0),
+        color: Color::rgba(0, 0, 0, 127),
+    };
+    assert_eq!(renderer.get_commands(), &[overlay]);
+}
+
+#[test]
+fn cross_fade_blends_new_states_over_old_ones_test() {
+    let mut state_machine = create_stack_state_machine();
+    let mut renderer = RecordingRenderer::new(Vector::new(800.0, 600.0));
+    state_machine.get_current_state_mut().get_stack_state_mut().exit_effect =
+        Some(TransitionEffect::CrossFade { duration: 1.0 });
+
+    transition(&mut state_machine, TestTransition::Replace);
+    state_machine.update(0.5);
+    state_machine.draw(&mut renderer, 1.0);
+
+    let faded = DrawCommand::Faded {
+        opacity: 0.5,
+        commands: vec![],
+    };
+    assert_eq!(renderer.get_commands(), &[faded]);
 }
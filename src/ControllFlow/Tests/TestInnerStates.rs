@@ -1,3 +1,5 @@
+use crate::ControllFlow::TransitionEffects::TransitionEffect;
+
 #[derive(Default)]
 pub struct TestState1InnerState{
     pub update_iteration: i32
@@ -11,8 +13,35 @@ impl TestState1InnerState{
 }
 
 
+pub struct TestState2InnerState;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TestTransition{
+    PushOverlay,
+    Pop,
+    Replace,
+    Quit,
+}
+
+/// Exits with the transition it is given, and counts what the state machine did with it.
 #[derive(Default)]
-pub struct TestState2InnerState{
-    is_entering: bool,
-    should_exit: bool,
+pub struct TestStackInnerState{
+    pub name: &'static str,
+    pub is_overlay: bool,
+    pub next_transition: Option<TestTransition>,
+    pub exit_effect: Option<TransitionEffect>,
+
+    pub enter_count: u32,
+    pub update_count: u32,
+    pub resume_count: u32,
+    pub quit_count: u32,
+    pub input_count: u32,
+    //"enter" and "draw", in the order they happened
+    pub calls: Vec<&'static str>,
+}
+
+impl TestStackInnerState{
+    pub fn new(name: &'static str, is_overlay: bool)->Self{
+        Self { name, is_overlay, ..Default::default() }
+    }
 }
\ No newline at end of file
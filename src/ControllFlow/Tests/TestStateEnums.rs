@@ -1,18 +1,39 @@
-use crate::{ControllFlow::{FlowState, State}, Objects::Interfaces::Drawable, InputSystem::{InputConsumer, Input}};
+use crate::{ControllFlow::{FlowState, State, Transition, TransitionEffects::TransitionEffect}, Objects::Interfaces::{Drawable, Renderer}, InputSystem::{InputConsumer, Input}};
 
-use super::TestInnerStates::{TestState1InnerState, TestState2InnerState};
+use super::TestInnerStates::{TestStackInnerState, TestState1InnerState, TestState2InnerState, TestTransition};
 
 pub enum TestStatesEnum {
     TestState1(TestState1InnerState),
     TestState2(TestState2InnerState),
+    Stack(TestStackInnerState),
+}
+
+impl TestStatesEnum {
+    pub fn get_stack_state(&self) -> &TestStackInnerState {
+        match self {
+            TestStatesEnum::Stack(inner_state) => inner_state,
+            _ => panic!("not a stack state"),
+        }
+    }
+
+    pub fn get_stack_state_mut(&mut self) -> &mut TestStackInnerState {
+        match self {
+            TestStatesEnum::Stack(inner_state) => inner_state,
+            _ => panic!("not a stack state"),
+        }
+    }
 }
 
 impl State<TestStatesEnum> for TestStatesEnum {
     fn onEnter(&mut self) {
-        println!("Entering")
+        println!("Entering");
+        if let TestStatesEnum::Stack(inner_state) = self {
+            inner_state.enter_count += 1;
+            inner_state.calls.push("enter");
+        }
     }
 
-    fn onUpdate(&mut self, delta_time: f32) -> FlowState {
+    fn onUpdate(&mut self, _delta_time: f32) -> FlowState {
         println!("Updating");
         match self {
             TestStatesEnum::TestState1(inner_state) => {
@@ -22,24 +43,64 @@ impl State<TestStatesEnum> for TestStatesEnum {
                     return FlowState::Update;
                 }
 
-                return FlowState::Exit;
+                FlowState::Exit
             }
-            TestStatesEnum::TestState2(inner_state) => {
+            TestStatesEnum::TestState2(_inner_state) => {
                 println!("entered second state");
-                return FlowState::Exit;
+                FlowState::Exit
+            }
+            TestStatesEnum::Stack(inner_state) => {
+                inner_state.update_count += 1;
+                if inner_state.next_transition.is_some() {
+                    return FlowState::Exit;
+                }
+
+                FlowState::Update
             }
         }
     }
 
-    fn onExit(&mut self) -> Option<TestStatesEnum> {
+    fn onExit(&mut self) -> Transition<TestStatesEnum> {
         println!("Exiting");
         match self {
             TestStatesEnum::TestState1(_) => {
-                Some(TestStatesEnum::TestState2(TestState2InnerState::default()))
+                Transition::Replace(TestStatesEnum::TestState2(TestState2InnerState))
             }
             TestStatesEnum::TestState2(_) => {
-                None
+                Transition::Pop
             }
+            TestStatesEnum::Stack(inner_state) => match inner_state.next_transition.take().unwrap() {
+                TestTransition::PushOverlay => Transition::Push(TestStatesEnum::Stack(TestStackInnerState::new("overlay", true))),
+                TestTransition::Pop => Transition::Pop,
+                TestTransition::Replace => Transition::Replace(TestStatesEnum::Stack(TestStackInnerState::new("replacement", false))),
+                TestTransition::Quit => Transition::Quit,
+            },
+        }
+    }
+
+    fn onResume(&mut self) {
+        if let TestStatesEnum::Stack(inner_state) = self {
+            inner_state.resume_count += 1;
+        }
+    }
+
+    fn get_transition_effect(&self) -> TransitionEffect {
+        match self {
+            TestStatesEnum::Stack(inner_state) => inner_state.exit_effect.unwrap_or(TransitionEffect::Cut),
+            _ => TransitionEffect::Cut,
+        }
+    }
+
+    fn onQuit(&mut self) {
+        if let TestStatesEnum::Stack(inner_state) = self {
+            inner_state.quit_count += 1;
+        }
+    }
+
+    fn is_overlay(&self) -> bool {
+        match self {
+            TestStatesEnum::Stack(inner_state) => inner_state.is_overlay,
+            _ => false,
         }
     }
 }
@@ -51,13 +112,17 @@ impl Default for TestStatesEnum {
 }
 
 impl Drawable for TestStatesEnum{
-    fn draw(&mut self, window: &mut sfml::graphics::RenderWindow) {
-        
+    fn draw(&mut self, _renderer: &mut dyn Renderer, _alpha: f32) {
+        if let TestStatesEnum::Stack(inner_state) = self {
+            inner_state.calls.push("draw");
+        }
     }
 }
 
 impl InputConsumer for TestStatesEnum{
-    fn handle_input(&mut self, input: &Input) {
-        
+    fn handle_input(&mut self, _input: &Input) {
+        if let TestStatesEnum::Stack(inner_state) = self {
+            inner_state.input_count += 1;
+        }
     }
 }
\ No newline at end of file
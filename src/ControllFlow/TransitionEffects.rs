@@ -0,0 +1,69 @@
+use sfml::graphics::Color;
+
+use crate::{MathUtilities::Vector, Objects::Interfaces::Renderer};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SlideDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl SlideDirection {
+    fn to_vector(self) -> Vector {
+        match self {
+            SlideDirection::Left => Vector::new(-1.0, 0.0),
+            SlideDirection::Right => Vector::new(1.0, 0.0),
+            SlideDirection::Up => Vector::new(0.0, -1.0),
+            SlideDirection::Down => Vector::new(0.0, 1.0),
+        }
+    }
+}
+
+/// How the screen goes from the states before a transition to the states after it. Durations
+/// are in seconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransitionEffect {
+    Cut,
+    /// The old states fade out to black during the first half and the new ones fade in from it.
+    FadeToBlack {
+        duration: f32,
+    },
+    CrossFade {
+        duration: f32,
+    },
+    /// The old states move out of the screen in the direction while the new ones follow them.
+    Slide {
+        duration: f32,
+        direction: SlideDirection,
+    },
+}
+
+impl TransitionEffect {
+    pub fn get_duration(&self) -> f32 {
+        match *self {
+            TransitionEffect::Cut => 0.0,
+            TransitionEffect::FadeToBlack { duration }
+            | TransitionEffect::CrossFade { duration }
+            | TransitionEffect::Slide { duration, .. } => duration,
+        }
+    }
+}
+
+pub fn draw_black_overlay(renderer: &mut dyn Renderer, opacity: f32) {
+    let size = renderer.get_size();
+    renderer.draw_rectangle(Vector::default(), size, Color::rgba(0, 0, 0, to_alpha(opacity)));
+}
+
+pub fn get_slide_offset(
+    renderer: &dyn Renderer,
+    direction: SlideDirection,
+    distance: f32,
+) -> Vector {
+    direction.to_vector() * renderer.get_size() * distance
+}
+
+fn to_alpha(opacity: f32) -> u8 {
+    (opacity.clamp(0.0, 1.0) * 255.0) as u8
+}
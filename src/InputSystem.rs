@@ -1,8 +1,18 @@
-use std::{collections::HashMap, hash::Hash, array};
+pub mod ActionTimings;
+pub mod Gamepad;
+pub mod KeyBindings;
+pub mod Replay;
+#[cfg(test)]
+mod Tests;
 
-use sfml::window::Key;
+use std::{hash::Hash, io, path::Path};
 
-use crate::Objects::Interfaces::{Updatable, Initializable};
+use serde::{Deserialize, Serialize};
+use sfml::{graphics::{RenderTarget, RenderWindow}, system::Vector2i, window::{mouse::Button, Event}};
+
+use crate::{MathUtilities::Vector, Objects::Interfaces::{Updatable, Initializable}};
+
+use self::{ActionTimings::ActionTimings as ActionTimingsTracker, Gamepad::{Gamepad as GamepadInput, SfmlJoystickSource}, KeyBindings::KeyBindings as ActionKeyBindings, Replay::{Replay as InputReplay, ReplayFrame, ReplayPlayback, ReplayRecorder, ReplaySetup}};
 
 #[derive(Default)]
 struct PreviousCurrentValue<T>{
@@ -12,8 +22,28 @@ struct PreviousCurrentValue<T>{
 
 pub trait InputConsumer{
     fn handle_input(&mut self, input: &Input);
+
+    /// Window events, forwarded before the update of the frame they happened in.
+    fn on_event(&mut self, _event: &Event){}
+
+    /// Bindings used while the consumer is receiving input.
+    fn get_input_context(&self)->InputContext{
+        InputContext::Gameplay
+    }
+}
+
+/// Set of bindings, so the same key can mean different actions in different parts of the game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputContext{
+    Menu,
+    Gameplay,
+    //active on top of every other context
+    Debug,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Keys{
     Left,
     Right,
@@ -21,24 +51,185 @@ pub enum Keys{
     Down,
     Enter,
     Esc,
+    ToggleDebug,
+    ToggleAim,
+    TogglePerformanceHud,
 
+    #[serde(skip)]
     Size,
 }
 
+impl Keys{
+    pub const ALL: [Keys; Keys::Size as usize] = [
+        Keys::Left,
+        Keys::Right,
+        Keys::Up,
+        Keys::Down,
+        Keys::Enter,
+        Keys::Esc,
+        Keys::ToggleDebug,
+        Keys::ToggleAim,
+        Keys::TogglePerformanceHud,
+    ];
+}
+
 pub struct Input{
     key_states: Vec<PreviousCurrentValue<bool>>,
 
-    key_value_sources: Vec<Box<dyn Fn()->bool>>,
+    key_bindings: ActionKeyBindings,
+    context: InputContext,
+    action_timings: ActionTimingsTracker,
+
+    gamepad: GamepadInput,
+
+    has_focus: bool,
+    mouse_position: Vector,
+    mouse_world_position: Vector,
+    mouse_buttons_down: Vec<Button>,
+    //pressed since the last update, kept even when released before it
+    mouse_buttons_pressed_this_frame: Vec<Button>,
+    mouse_button_states: PreviousCurrentValue<Vec<Button>>,
+
+    delta_time: f32,
+    recorder: Option<ReplayRecorder>,
+    playback: Option<ReplayPlayback>,
 }
 
 impl Input{
     pub fn new()->Self{
         Self{
             key_states: vec![],
-            key_value_sources: vec![],
+            key_bindings: ActionKeyBindings::default(),
+            context: InputContext::Menu,
+            action_timings: ActionTimingsTracker::new(),
+            gamepad: GamepadInput::new(Box::new(SfmlJoystickSource)),
+            has_focus: true,
+            mouse_position: Vector::default(),
+            mouse_world_position: Vector::default(),
+            mouse_buttons_down: vec![],
+            mouse_buttons_pressed_this_frame: vec![],
+            mouse_button_states: PreviousCurrentValue::default(),
+            delta_time: 0.0,
+            recorder: None,
+            playback: None,
         }
     }
 
+    /// Every following frame is recorded until `stop_recording`.
+    pub fn start_recording(&mut self, path: &Path, seed: u64, setup: ReplaySetup){
+        self.recorder = Some(ReplayRecorder::new(path, seed, setup));
+    }
+
+    pub fn stop_recording(&mut self)->io::Result<()>{
+        match self.recorder.take() {
+            Some(recorder) => recorder.save(),
+            None => Ok(()),
+        }
+    }
+
+    /// Following frames are read from the replay instead of the keyboard, the mouse and the
+    /// gamepad, until it runs out.
+    pub fn start_playback(&mut self, replay: InputReplay){
+        self.playback = Some(ReplayPlayback::new(replay));
+    }
+
+    pub fn is_replaying(&self)->bool{
+        self.playback.is_some()
+    }
+
+    /// Time of the current frame, which comes from the replay while one is playing.
+    pub fn get_delta_time(&self)->f32{
+        self.delta_time
+    }
+
+    pub fn on_event(&mut self, event: &Event){
+        match *event {
+            Event::LostFocus => self.has_focus = false,
+            Event::GainedFocus => self.has_focus = true,
+            Event::MouseMoved { x, y } => {
+                self.mouse_position = Vector::new(x as f32, y as f32);
+            }
+            Event::MouseButtonPressed { button, x, y } => {
+                self.mouse_position = Vector::new(x as f32, y as f32);
+                if !self.mouse_buttons_down.contains(&button){
+                    self.mouse_buttons_down.push(button);
+                }
+                if !self.mouse_buttons_pressed_this_frame.contains(&button){
+                    self.mouse_buttons_pressed_this_frame.push(button);
+                }
+            }
+            Event::MouseButtonReleased { button, x, y } => {
+                self.mouse_position = Vector::new(x as f32, y as f32);
+                self.mouse_buttons_down.retain(|down_button| *down_button != button);
+            }
+            _ => {}
+        }
+    }
+
+    /// Maps the mouse through the view the window currently uses, call it once per frame
+    /// after the events were handled.
+    pub fn update_mouse_world_position(&mut self, window: &RenderWindow){
+        let mouse_position = self.get_mouse_position();
+        let pixel = Vector2i::new(mouse_position.get_x() as i32, mouse_position.get_y() as i32);
+        let world_position = window.map_pixel_to_coords_current_view(pixel);
+        self.mouse_world_position = Vector::new(world_position.x, world_position.y);
+    }
+
+    /// Position of the mouse in the window, in pixels.
+    pub fn get_mouse_position(&self)->Vector{
+        self.mouse_position
+    }
+
+    pub fn get_mouse_world_position(&self)->Vector{
+        self.mouse_world_position
+    }
+
+    pub fn is_mouse_button_pressed(&self, button: Button)->bool{
+        self.mouse_button_states.current_value.contains(&button)
+    }
+
+    pub fn mouse_button_just_pressed(&self, button: Button)->bool{
+        self.is_mouse_button_pressed(button) && !self.mouse_button_states.previous_value.contains(&button)
+    }
+
+    pub fn has_focus(&self)->bool{
+        self.has_focus
+    }
+
+    /// Analog stick when it is pushed, otherwise the direction actions. Never longer than 1.
+    pub fn movement_vector(&self)->Vector{
+        let stick = self.gamepad.movement_vector();
+        if stick.length() > 0.0{
+            return stick;
+        }
+
+        let mut direction = Vector::new(0.0, 0.0);
+        if self.is_pressed(Keys::Up){
+            direction += Vector::new(0.0, -1.0);
+        }
+        if self.is_pressed(Keys::Down){
+            direction += Vector::new(0.0, 1.0);
+        }
+        if self.is_pressed(Keys::Left){
+            direction += Vector::new(-1.0, 0.0);
+        }
+        if self.is_pressed(Keys::Right){
+            direction += Vector::new(1.0, 0.0);
+        }
+
+        direction.normal().unwrap_or_default()
+    }
+
+    /// Takes effect with the next update, the bindings are saved with the rest of the settings.
+    pub fn set_key_bindings(&mut self, key_bindings: ActionKeyBindings){
+        self.key_bindings = key_bindings;
+    }
+
+    /// Takes effect with the next update.
+    pub fn set_context(&mut self, context: InputContext){
+        self.context = context;
+    }
+
     pub fn is_pressed(&self, key: Keys)->bool{
         let index = key as usize;
 
@@ -52,13 +243,109 @@ impl Input{
         has_been_just_pressed
     }
 
+    pub fn just_released(&self, key: Keys)->bool{
+        let key_state = self.key_states.get(key as usize).unwrap();
+        !key_state.current_value && key_state.previous_value
+    }
+
+    pub fn is_held_for(&self, key: Keys, duration: f32)->bool{
+        self.is_pressed(key) && self.action_timings.get_held_time(key) >= duration
+    }
+
+    /// True on the second press when it comes within the double tap window of the first one.
+    pub fn double_tapped(&self, key: Keys)->bool{
+        self.action_timings.is_double_tapped(key)
+    }
+
+    pub fn is_chord_pressed(&self, keys: &[Keys])->bool{
+        keys.iter().all(|key| self.is_pressed(*key))
+    }
+
+    /// True on the frame the last key of the chord goes down, whatever order they were pressed in.
+    pub fn chord_just_pressed(&self, keys: &[Keys])->bool{
+        self.is_chord_pressed(keys) && keys.iter().any(|key| self.just_pressed(*key))
+    }
+
     fn update_current_keys_states(&mut self){
-        let keys_count = Keys::Size as usize;
-        for i in 0..keys_count{
-            let get_current_key_state_function = self.key_value_sources.get(i).unwrap();
-            let value =  get_current_key_state_function();
-            self.key_states.get_mut(i).unwrap().current_value = value;
+        let active_contexts = [self.context, InputContext::Debug];
+        for action in Keys::ALL{
+            let is_key_pressed = active_contexts.iter().any(|context| {
+                self.key_bindings.get_keys(*context, action).iter().any(|key| key.is_pressed())
+            });
+            //keys are read globally, so without focus they would come from other applications
+            let value = self.has_focus && (is_key_pressed || self.gamepad.is_action_pressed(action));
+            self.key_states.get_mut(action as usize).unwrap().current_value = value;
+        }
+    }
+
+    fn update_from_replay(&mut self)->bool{
+        let frame = match self.playback.as_mut().and_then(|playback| playback.next_frame()) {
+            Some(frame) => frame.clone(),
+            None => {
+                //live input takes over once the replay runs out
+                self.playback = None;
+                return false;
+            }
+        };
+
+        self.delta_time = frame.delta_time;
+        self.has_focus = frame.has_focus;
+        self.mouse_world_position = Vector::new(frame.mouse_world_position.0, frame.mouse_world_position.1);
+        self.mouse_button_states.previous_value = std::mem::replace(&mut self.mouse_button_states.current_value, frame.get_mouse_buttons());
+        //clicks made while watching the replay don't carry over to the live input
+        self.mouse_buttons_pressed_this_frame.clear();
+
+        self.update_previous_keyStates();
+        for action in Keys::ALL{
+            self.key_states.get_mut(action as usize).unwrap().current_value = frame.pressed_actions.contains(&action);
+        }
+
+        true
+    }
+
+    fn create_replay_frame(&self)->ReplayFrame{
+        let mut frame = ReplayFrame::default();
+        frame.delta_time = self.delta_time;
+        frame.pressed_actions = Keys::ALL.into_iter().filter(|action| self.is_pressed(*action)).collect();
+        frame.has_focus = self.has_focus;
+        frame.mouse_world_position = (self.mouse_world_position.get_x(), self.mouse_world_position.get_y());
+        frame.set_mouse_buttons(&self.mouse_button_states.current_value);
+        frame
+    }
+
+    fn record_frame(&mut self){
+        if self.recorder.is_none(){
+            return;
+        }
+
+        let frame = self.create_replay_frame();
+        self.recorder.as_mut().unwrap().record(frame);
+    }
+
+    fn update_from_devices(&mut self, delta_time: f32){
+        self.delta_time = delta_time;
+        self.gamepad.update();
+        self.update_mouse_button_states();
+        self.update_previous_keyStates();
+        self.update_current_keys_states();
+        self.record_frame();
+    }
+
+    fn update_mouse_button_states(&mut self){
+        self.mouse_button_states.previous_value = std::mem::take(&mut self.mouse_button_states.current_value);
+        let pressed_this_frame = std::mem::take(&mut self.mouse_buttons_pressed_this_frame);
+        if !self.has_focus {
+            return;
+        }
+
+        //a click shorter than a frame still counts as pressed for that frame
+        let mut current_buttons = self.mouse_buttons_down.clone();
+        for button in pressed_this_frame {
+            if !current_buttons.contains(&button){
+                current_buttons.push(button);
+            }
         }
+        self.mouse_button_states.current_value = current_buttons;
     }
 
     fn update_previous_keyStates(&mut self){
@@ -67,42 +354,23 @@ impl Input{
             self.key_states.get_mut(i).unwrap().previous_value = self.key_states.get(i).unwrap().current_value;
         }
     }
-
-    fn get_key(key: &Key){
-        return
-    }
 }
 
 impl Initializable for Input{
     fn initialize(&mut self) {
         let keys_count = Keys::Size as usize;
-        for i in 0..keys_count{
+        for _ in 0..keys_count{
             self.key_states.push(PreviousCurrentValue::default());
-            self.key_value_sources.push(Box::new(|| Key::ESCAPE.is_pressed())); //ESCAPE just to initialize vector with some random key
         }
-
-        let w_supplier = Box::new(|| Key::W.is_pressed());
-        let s_supplier = Box::new(|| Key::S.is_pressed());
-        let a_supplier = Box::new(|| Key::A.is_pressed());
-        let d_supplier = Box::new(|| Key::D.is_pressed());
-        let enter_supplier = Box::new(|| Key::ENTER.is_pressed());
-        let esc_supplier = Box::new(|| Key::ESCAPE.is_pressed());
-
-
-
-        self.key_value_sources.insert(Keys::Left as usize, a_supplier);
-        self.key_value_sources.insert(Keys::Right as usize, d_supplier);
-        self.key_value_sources.insert(Keys::Up as usize, w_supplier);
-        self.key_value_sources.insert(Keys::Down as usize, s_supplier);
-        self.key_value_sources.insert(Keys::Enter as usize, enter_supplier);
-        self.key_value_sources.insert(Keys::Esc as usize, esc_supplier);
-
     }
 }
 
 impl Updatable for Input{
     fn update(&mut self, delta_time: f32) {
-        self.update_previous_keyStates();
-        self.update_current_keys_states();
+        if !self.update_from_replay(){
+            self.update_from_devices(delta_time);
+        }
+
+        self.action_timings.update(&self.key_states, self.delta_time);
     }
 }
\ No newline at end of file
@@ -0,0 +1,57 @@
+use super::{Keys, PreviousCurrentValue};
+
+/// How long each action has been held and when it was last pressed, for the detectors that
+/// need more than the current and the previous frame.
+pub struct ActionTimings {
+    time: f32,
+    held_times: Vec<f32>,
+    last_press_times: Vec<Option<f32>>,
+    double_taps: Vec<bool>,
+
+    /// Longest time, in seconds, between two presses making a double tap.
+    pub double_tap_window: f32,
+}
+
+impl ActionTimings {
+    pub const DEFAULT_DOUBLE_TAP_WINDOW: f32 = 0.3;
+
+    pub fn new() -> Self {
+        let keys_count = Keys::Size as usize;
+        Self {
+            time: 0.0,
+            held_times: vec![0.0; keys_count],
+            last_press_times: vec![None; keys_count],
+            double_taps: vec![false; keys_count],
+            double_tap_window: Self::DEFAULT_DOUBLE_TAP_WINDOW,
+        }
+    }
+
+    pub(super) fn update(&mut self, key_states: &[PreviousCurrentValue<bool>], delta_time: f32) {
+        self.time += delta_time;
+
+        for (index, key_state) in key_states.iter().enumerate() {
+            self.held_times[index] = match (key_state.previous_value, key_state.current_value) {
+                (true, true) => self.held_times[index] + delta_time,
+                _ => 0.0,
+            };
+
+            self.double_taps[index] = false;
+            if key_state.current_value && !key_state.previous_value {
+                let is_double_tap = self.last_press_times[index]
+                    .is_some_and(|last_press| self.time - last_press <= self.double_tap_window);
+
+                self.double_taps[index] = is_double_tap;
+                //the press finishing a double tap doesn't start the next one
+                self.last_press_times[index] = if is_double_tap { None } else { Some(self.time) };
+            }
+        }
+    }
+
+    pub fn get_held_time(&self, action: Keys) -> f32 {
+        self.held_times[action as usize]
+    }
+
+    pub fn is_double_tapped(&self, action: Keys) -> bool {
+        self.double_taps[action as usize]
+    }
+}
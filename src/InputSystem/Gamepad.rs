@@ -0,0 +1,132 @@
+use sfml::window::joystick::{self, Axis};
+
+use crate::MathUtilities::Vector;
+
+use super::Keys;
+
+//xbox layout, which is what most pads report: A, B, start and back
+const BUTTON_BINDINGS: [(Keys, &[u32]); 2] = [(Keys::Enter, &[0, 7]), (Keys::Esc, &[1, 6])];
+
+/// Where the gamepad state comes from, so tests can drive `Gamepad` without a real controller.
+/// Axis positions are in SFML's range, from -100 to 100.
+pub trait JoystickSource {
+    fn is_connected(&self, joystick_id: u32) -> bool;
+    fn axis_position(&self, joystick_id: u32, axis: Axis) -> f32;
+    fn is_button_pressed(&self, joystick_id: u32, button: u32) -> bool;
+}
+
+pub struct SfmlJoystickSource;
+
+impl JoystickSource for SfmlJoystickSource {
+    fn is_connected(&self, joystick_id: u32) -> bool {
+        joystick::is_connected(joystick_id)
+    }
+
+    fn axis_position(&self, joystick_id: u32, axis: Axis) -> f32 {
+        joystick::axis_position(joystick_id, axis)
+    }
+
+    fn is_button_pressed(&self, joystick_id: u32, button: u32) -> bool {
+        joystick::is_button_pressed(joystick_id, button)
+    }
+}
+
+pub struct Gamepad {
+    source: Box<dyn JoystickSource>,
+    joystick_id: Option<u32>,
+
+    /// Part of the stick range, from 0 to 1, ignored around the center.
+    pub dead_zone: f32,
+    /// How far the stick or the d-pad has to go to press a direction action.
+    pub digital_threshold: f32,
+}
+
+impl Gamepad {
+    pub const DEFAULT_DEAD_ZONE: f32 = 0.2;
+    pub const DEFAULT_DIGITAL_THRESHOLD: f32 = 0.5;
+
+    pub fn new(source: Box<dyn JoystickSource>) -> Self {
+        Self {
+            source,
+            joystick_id: None,
+            dead_zone: Self::DEFAULT_DEAD_ZONE,
+            digital_threshold: Self::DEFAULT_DIGITAL_THRESHOLD,
+        }
+    }
+
+    /// Keeps using the same controller while it stays connected and picks the first connected
+    /// one otherwise, so controllers can be plugged and unplugged while playing.
+    pub fn update(&mut self) {
+        if let Some(joystick_id) = self.joystick_id {
+            if self.source.is_connected(joystick_id) {
+                return;
+            }
+        }
+
+        self.joystick_id = (0..joystick::COUNT).find(|id| self.source.is_connected(*id));
+    }
+
+    #[cfg(test)]
+    pub fn get_joystick_id(&self) -> Option<u32> {
+        self.joystick_id
+    }
+
+    /// Left stick with the dead zone cut out and the rest stretched back to the full range.
+    /// Its length is never above 1.
+    pub fn movement_vector(&self) -> Vector {
+        let joystick_id = match self.joystick_id {
+            Some(joystick_id) => joystick_id,
+            None => return Vector::default(),
+        };
+
+        let stick = Vector::new(
+            self.source.axis_position(joystick_id, Axis::X),
+            self.source.axis_position(joystick_id, Axis::Y),
+        ) * 0.01;
+
+        let length = f32::min(stick.length(), 1.0);
+        if length <= self.dead_zone {
+            return Vector::default();
+        }
+
+        let scaled_length = (length - self.dead_zone) / (1.0 - self.dead_zone);
+        stick.normal().unwrap_or_default() * scaled_length
+    }
+
+    pub fn is_action_pressed(&self, action: Keys) -> bool {
+        let joystick_id = match self.joystick_id {
+            Some(joystick_id) => joystick_id,
+            None => return false,
+        };
+
+        let is_button_pressed = BUTTON_BINDINGS
+            .iter()
+            .filter(|(bound_action, _)| *bound_action == action)
+            .flat_map(|(_, buttons)| buttons.iter())
+            .any(|button| self.source.is_button_pressed(joystick_id, *button));
+
+        is_button_pressed || self.is_direction_pressed(joystick_id, action)
+    }
+
+    fn is_direction_pressed(&self, joystick_id: u32, action: Keys) -> bool {
+        let stick = self.movement_vector();
+        //sfml reports the d-pad up as a positive y, unlike the sticks
+        let pad = Vector::new(
+            self.source.axis_position(joystick_id, Axis::POV_X),
+            -self.source.axis_position(joystick_id, Axis::POV_Y),
+        ) * 0.01;
+
+        let threshold = self.digital_threshold;
+        let is_pushed = |direction: Vector| {
+            stick.dot(direction) >= threshold || pad.dot(direction) >= threshold
+        };
+
+        match action {
+            Keys::Left => is_pushed(Vector::new(-1.0, 0.0)),
+            Keys::Right => is_pushed(Vector::new(1.0, 0.0)),
+            Keys::Up => is_pushed(Vector::new(0.0, -1.0)),
+            Keys::Down => is_pushed(Vector::new(0.0, 1.0)),
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::Path,
+};
+
+use serde::{
+    de::{
+        value::{Error as ValueError, StrDeserializer},
+        DeserializeOwned, IntoDeserializer,
+    },
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use sfml::window::Key;
+
+use super::{InputContext, Keys};
+use crate::Objects::Log;
+
+pub const KEY_BINDINGS_PATH: &str = "resources/Input/KeyBindings.json";
+
+/// Physical keys triggering each action in each context, any of them being pressed presses
+/// the action while its context is active.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyBindings {
+    bindings: HashMap<InputContext, HashMap<Keys, Vec<Key>>>,
+}
+
+impl KeyBindings {
+    /// Actions missing from the file, or the whole file when it can't be read, keep the defaults.
+    pub fn load(path: &Path) -> Self {
+        let json = match fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(_) => return Self::default(),
+        };
+
+        Self::from_json(&json).unwrap_or_else(|error| {
+            Log::warn(&format!(
+                "couldn't read the key bindings {}: {}",
+                path.display(),
+                error
+            ));
+            Self::default()
+        })
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    //unknown names are skipped one by one, so one typo doesn't throw away the whole file
+    fn from_key_names(key_names: BTreeMap<String, BTreeMap<String, Vec<String>>>) -> Self {
+        let mut key_bindings = Self::default();
+        for (context_name, actions) in key_names {
+            let context: InputContext = match parse_name(&context_name) {
+                Some(context) => context,
+                None => {
+                    Log::warn(&format!(
+                        "unknown input context {}, its bindings are skipped",
+                        context_name
+                    ));
+                    continue;
+                }
+            };
+
+            for (action_name, names) in actions {
+                let action: Keys = match parse_name(&action_name) {
+                    Some(action) => action,
+                    None => {
+                        Log::warn(&format!(
+                            "unknown action {}, its keys are skipped",
+                            action_name
+                        ));
+                        continue;
+                    }
+                };
+
+                let keys: Vec<Key> = names
+                    .iter()
+                    .filter_map(|name| {
+                        let key = key_from_name(name);
+                        if key.is_none() {
+                            Log::warn(&format!(
+                                "unknown key {} bound to {}, it is skipped",
+                                name, action_name
+                            ));
+                        }
+                        key
+                    })
+                    .collect();
+
+                //an action without any key couldn't be pressed anymore
+                if keys.is_empty() {
+                    Log::warn(&format!(
+                        "no known key bound to {}, it keeps its default keys",
+                        action_name
+                    ));
+                    continue;
+                }
+                key_bindings.rebind(context, action, keys);
+            }
+        }
+
+        key_bindings
+    }
+
+    fn get_key_names(&self) -> BTreeMap<InputContext, BTreeMap<Keys, Vec<&'static str>>> {
+        self.bindings
+            .iter()
+            .map(|(context, actions)| {
+                let actions = actions
+                    .iter()
+                    .map(|(action, keys)| {
+                        let names = keys.iter().filter_map(|key| key_name(*key)).collect();
+                        (*action, names)
+                    })
+                    .collect();
+                (*context, actions)
+            })
+            .collect()
+    }
+
+    pub fn get_keys(&self, context: InputContext, action: Keys) -> &[Key] {
+        match self
+            .bindings
+            .get(&context)
+            .and_then(|actions| actions.get(&action))
+        {
+            Some(keys) => keys,
+            None => &[],
+        }
+    }
+
+    pub fn rebind(&mut self, context: InputContext, action: Keys, keys: Vec<Key>) {
+        self.bindings
+            .entry(context)
+            .or_default()
+            .insert(action, keys);
+    }
+
+    pub fn add_key(&mut self, context: InputContext, action: Keys, key: Key) {
+        let keys = self
+            .bindings
+            .entry(context)
+            .or_default()
+            .entry(action)
+            .or_default();
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    pub fn remove_key(&mut self, context: InputContext, action: Keys, key: Key) {
+        if let Some(keys) = self
+            .bindings
+            .get_mut(&context)
+            .and_then(|actions| actions.get_mut(&action))
+        {
+            keys.retain(|bound_key| *bound_key != key);
+        }
+    }
+}
+
+//keys are written by name, sfml's codes aren't meant to be read by people
+impl Serialize for KeyBindings {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.get_key_names().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyBindings {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let key_names = BTreeMap::deserialize(deserializer)?;
+        Ok(Self::from_key_names(key_names))
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let menu = HashMap::from([
+            (Keys::Up, vec![Key::W, Key::UP]),
+            (Keys::Down, vec![Key::S, Key::DOWN]),
+            (Keys::Left, vec![Key::A, Key::LEFT]),
+            (Keys::Right, vec![Key::D, Key::RIGHT]),
+            (Keys::Enter, vec![Key::ENTER, Key::SPACE]),
+            (Keys::Esc, vec![Key::ESCAPE]),
+        ]);
+        let gameplay = HashMap::from([
+            (Keys::Left, vec![Key::A, Key::LEFT]),
+            (Keys::Right, vec![Key::D, Key::RIGHT]),
+            (Keys::Up, vec![Key::W, Key::UP]),
+            (Keys::Down, vec![Key::S, Key::DOWN]),
+            (Keys::Enter, vec![Key::ENTER, Key::SPACE]),
+            (Keys::Esc, vec![Key::ESCAPE]),
+            (Keys::ToggleAim, vec![Key::T]),
+        ]);
+        let debug = HashMap::from([
+            (Keys::ToggleDebug, vec![Key::F3]),
+            (Keys::TogglePerformanceHud, vec![Key::F2]),
+        ]);
+
+        let bindings = HashMap::from([
+            (InputContext::Menu, menu),
+            (InputContext::Gameplay, gameplay),
+            (InputContext::Debug, debug),
+        ]);
+
+        Self { bindings }
+    }
+}
+
+//contexts and actions are written the way serde writes them
+fn parse_name<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let deserializer: StrDeserializer<ValueError> = name.into_deserializer();
+    T::deserialize(deserializer).ok()
+}
+
+//every key sfml knows, so any pressed key can be saved
+const KEY_NAMES: [(&str, Key); 101] = [
+    ("A", Key::A),
+    ("B", Key::B),
+    ("C", Key::C),
+    ("D", Key::D),
+    ("E", Key::E),
+    ("F", Key::F),
+    ("G", Key::G),
+    ("H", Key::H),
+    ("I", Key::I),
+    ("J", Key::J),
+    ("K", Key::K),
+    ("L", Key::L),
+    ("M", Key::M),
+    ("N", Key::N),
+    ("O", Key::O),
+    ("P", Key::P),
+    ("Q", Key::Q),
+    ("R", Key::R),
+    ("S", Key::S),
+    ("T", Key::T),
+    ("U", Key::U),
+    ("V", Key::V),
+    ("W", Key::W),
+    ("X", Key::X),
+    ("Y", Key::Y),
+    ("Z", Key::Z),
+    ("Num0", Key::NUM0),
+    ("Num1", Key::NUM1),
+    ("Num2", Key::NUM2),
+    ("Num3", Key::NUM3),
+    ("Num4", Key::NUM4),
+    ("Num5", Key::NUM5),
+    ("Num6", Key::NUM6),
+    ("Num7", Key::NUM7),
+    ("Num8", Key::NUM8),
+    ("Num9", Key::NUM9),
+    ("Escape", Key::ESCAPE),
+    ("LControl", Key::LCONTROL),
+    ("LShift", Key::LSHIFT),
+    ("LAlt", Key::LALT),
+    ("RControl", Key::RCONTROL),
+    ("RShift", Key::RSHIFT),
+    ("RAlt", Key::RALT),
+    ("LSystem", Key::LSYSTEM),
+    ("RSystem", Key::RSYSTEM),
+    ("Menu", Key::MENU),
+    ("Space", Key::SPACE),
+    ("Enter", Key::ENTER),
+    ("Backspace", Key::BACKSPACE),
+    ("Tab", Key::TAB),
+    ("Comma", Key::COMMA),
+    ("Period", Key::PERIOD),
+    ("Semicolon", Key::SEMICOLON),
+    ("LBracket", Key::LBRACKET),
+    ("RBracket", Key::RBRACKET),
+    ("Quote", Key::QUOTE),
+    ("Slash", Key::SLASH),
+    ("Backslash", Key::BACKSLASH),
+    ("Tilde", Key::TILDE),
+    ("Equal", Key::EQUAL),
+    ("Hyphen", Key::HYPHEN),
+    ("Left", Key::LEFT),
+    ("Right", Key::RIGHT),
+    ("Up", Key::UP),
+    ("Down", Key::DOWN),
+    ("Add", Key::ADD),
+    ("Subtract", Key::SUBTRACT),
+    ("Multiply", Key::MULTIPLY),
+    ("Divide", Key::DIVIDE),
+    ("Numpad0", Key::NUMPAD0),
+    ("Numpad1", Key::NUMPAD1),
+    ("Numpad2", Key::NUMPAD2),
+    ("Numpad3", Key::NUMPAD3),
+    ("Numpad4", Key::NUMPAD4),
+    ("Numpad5", Key::NUMPAD5),
+    ("Numpad6", Key::NUMPAD6),
+    ("Numpad7", Key::NUMPAD7),
+    ("Numpad8", Key::NUMPAD8),
+    ("Numpad9", Key::NUMPAD9),
+    ("F1", Key::F1),
+    ("F2", Key::F2),
+    ("F3", Key::F3),
+    ("F4", Key::F4),
+    ("F5", Key::F5),
+    ("F6", Key::F6),
+    ("F7", Key::F7),
+    ("F8", Key::F8),
+    ("F9", Key::F9),
+    ("F10", Key::F10),
+    ("F11", Key::F11),
+    ("F12", Key::F12),
+    ("F13", Key::F13),
+    ("F14", Key::F14),
+    ("F15", Key::F15),
+    ("PageUp", Key::PAGEUP),
+    ("PageDown", Key::PAGEDOWN),
+    ("Home", Key::HOME),
+    ("End", Key::END),
+    ("Insert", Key::INSERT),
+    ("Delete", Key::DELETE),
+    ("Pause", Key::PAUSE),
+];
+
+pub fn key_from_name(name: &str) -> Option<Key> {
+    KEY_NAMES
+        .iter()
+        .find(|(key_name, _)| key_name.eq_ignore_ascii_case(name))
+        .map(|(_, key)| *key)
+}
+
+pub fn key_name(key: Key) -> Option<&'static str> {
+    KEY_NAMES
+        .iter()
+        .find(|(_, named_key)| *named_key == key)
+        .map(|(name, _)| *name)
+}
@@ -0,0 +1,155 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sfml::window::mouse::Button;
+
+use crate::Objects::FixedTimestep::FixedTimestep;
+
+use super::Keys;
+
+//buttons are saved as their index in this table
+const MOUSE_BUTTONS: [Button; 5] = [
+    Button::LEFT,
+    Button::RIGHT,
+    Button::MIDDLE,
+    Button::X_BUTTON_1,
+    Button::X_BUTTON_2,
+];
+
+/// Everything `Input` read during one frame.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ReplayFrame {
+    pub delta_time: f32,
+    pub pressed_actions: Vec<Keys>,
+    pub has_focus: bool,
+    pub mouse_world_position: (f32, f32),
+    mouse_buttons: Vec<usize>,
+}
+
+impl ReplayFrame {
+    pub fn get_mouse_buttons(&self) -> Vec<Button> {
+        self.mouse_buttons
+            .iter()
+            .filter_map(|index| MOUSE_BUTTONS.get(*index).copied())
+            .collect()
+    }
+
+    pub fn set_mouse_buttons(&mut self, buttons: &[Button]) {
+        self.mouse_buttons = buttons
+            .iter()
+            .filter_map(|button| MOUSE_BUTTONS.iter().position(|known| known == button))
+            .collect();
+    }
+}
+
+/// How the run was started, besides the seed. Playing the input back with anything else would
+/// make the run diverge.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "snake_case")]
+pub struct ReplaySetup {
+    pub simulation_rate: f32,
+    //the default level when missing
+    pub level_path: Option<PathBuf>,
+    pub skip_intro: bool,
+}
+
+impl Default for ReplaySetup {
+    fn default() -> Self {
+        Self {
+            simulation_rate: FixedTimestep::DEFAULT_RATE,
+            level_path: None,
+            skip_intro: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Replay {
+    pub seed: u64,
+    //replays recorded before the setup was saved were all started the default way
+    #[serde(default)]
+    pub setup: ReplaySetup,
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            setup: ReplaySetup::default(),
+            frames: vec![],
+        }
+    }
+
+    pub fn with_setup(mut self, setup: ReplaySetup) -> Self {
+        self.setup = setup;
+        self
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        let replay: Self = serde_json::from_str(&json)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+        let rate = replay.setup.simulation_rate;
+        if !(rate > 0.0 && rate.is_finite()) {
+            let error = format!("the replay has an invalid simulation rate {}", rate);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, error));
+        }
+        Ok(replay)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+    }
+}
+
+pub struct ReplayRecorder {
+    replay: Replay,
+    path: PathBuf,
+}
+
+impl ReplayRecorder {
+    pub fn new(path: &Path, seed: u64, setup: ReplaySetup) -> Self {
+        Self {
+            replay: Replay::new(seed).with_setup(setup),
+            path: path.to_path_buf(),
+        }
+    }
+
+    pub fn record(&mut self, frame: ReplayFrame) {
+        self.replay.frames.push(frame);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        self.replay.save(&self.path)
+    }
+}
+
+pub struct ReplayPlayback {
+    replay: Replay,
+    next_frame: usize,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            next_frame: 0,
+        }
+    }
+
+    pub fn next_frame(&mut self) -> Option<&ReplayFrame> {
+        let frame = self.replay.frames.get(self.next_frame)?;
+        self.next_frame += 1;
+        Some(frame)
+    }
+}
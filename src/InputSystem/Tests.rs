@@ -0,0 +1,500 @@
+use std::{
+    cell::RefCell,
+    env, fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use sfml::window::{joystick::Axis, mouse::Button, Event, Key};
+
+use super::{
+    Gamepad::{Gamepad, JoystickSource},
+    Input, InputContext,
+    KeyBindings::{key_from_name, key_name, KeyBindings, KEY_BINDINGS_PATH},
+    Keys,
+    Replay::{Replay, ReplayFrame, ReplaySetup},
+};
+use crate::{
+    MathUtilities::Vector,
+    Objects::Interfaces::{Initializable, Updatable},
+};
+
+#[test]
+fn key_bindings_json_round_trip_test() {
+    let mut key_bindings = KeyBindings::default();
+    key_bindings.rebind(InputContext::Gameplay, Keys::Up, vec![Key::Z, Key::UP]);
+    key_bindings.add_key(InputContext::Gameplay, Keys::Left, Key::Q);
+
+    let json = serde_json::to_string(&key_bindings).unwrap();
+    let loaded = KeyBindings::from_json(&json).unwrap();
+
+    assert_eq!(loaded, key_bindings);
+    assert_eq!(
+        loaded.get_keys(InputContext::Gameplay, Keys::Up),
+        &[Key::Z, Key::UP]
+    );
+    assert_eq!(
+        loaded.get_keys(InputContext::Gameplay, Keys::Left),
+        &[Key::A, Key::LEFT, Key::Q]
+    );
+    //contexts are independent of each other
+    assert_eq!(
+        loaded.get_keys(InputContext::Menu, Keys::Up),
+        &[Key::W, Key::UP]
+    );
+}
+
+#[test]
+fn key_bindings_missing_actions_keep_defaults_test() {
+    let key_bindings = KeyBindings::from_json(r#"{ "menu": { "up": ["z", "NotAKey"] } }"#).unwrap();
+
+    assert_eq!(
+        key_bindings.get_keys(InputContext::Menu, Keys::Up),
+        &[Key::Z]
+    );
+    assert_eq!(
+        key_bindings.get_keys(InputContext::Menu, Keys::Down),
+        KeyBindings::default().get_keys(InputContext::Menu, Keys::Down)
+    );
+    assert_eq!(
+        key_bindings.get_keys(InputContext::Gameplay, Keys::Up),
+        KeyBindings::default().get_keys(InputContext::Gameplay, Keys::Up)
+    );
+}
+
+#[test]
+fn key_bindings_skip_unknown_entries_test() {
+    let key_bindings = KeyBindings::from_json(
+        r#"{
+            "sideways": { "up": ["Z"] },
+            "menu": { "jump": ["J"], "down": ["NotAKey"], "up": [], "enter": ["E"] }
+        }"#,
+    )
+    .unwrap();
+
+    //the known entries next to the unknown ones are still read
+    assert_eq!(
+        key_bindings.get_keys(InputContext::Menu, Keys::Enter),
+        &[Key::E]
+    );
+    //an action left without a known key keeps its defaults
+    for action in [Keys::Up, Keys::Down] {
+        assert_eq!(
+            key_bindings.get_keys(InputContext::Menu, action),
+            KeyBindings::default().get_keys(InputContext::Menu, action)
+        );
+    }
+    assert_eq!(
+        key_bindings.get_keys(InputContext::Gameplay, Keys::Up),
+        KeyBindings::default().get_keys(InputContext::Gameplay, Keys::Up)
+    );
+}
+
+#[test]
+fn key_bindings_unreadable_file_uses_defaults_test() {
+    let key_bindings = KeyBindings::load(Path::new("resources/Input/DoesNotExist.json"));
+    assert_eq!(key_bindings, KeyBindings::default());
+
+    assert!(KeyBindings::from_json("not json").is_err());
+}
+
+#[test]
+fn every_action_has_default_keys_test() {
+    let key_bindings = KeyBindings::default();
+    for action in Keys::ALL {
+        let is_bound = [
+            InputContext::Menu,
+            InputContext::Gameplay,
+            InputContext::Debug,
+        ]
+        .iter()
+        .any(|context| !key_bindings.get_keys(*context, action).is_empty());
+        assert!(is_bound);
+    }
+}
+
+#[test]
+fn shipped_key_bindings_match_defaults_test() {
+    assert_eq!(
+        KeyBindings::load(Path::new(KEY_BINDINGS_PATH)),
+        KeyBindings::default()
+    );
+}
+
+#[test]
+fn key_bindings_add_and_remove_keys_test() {
+    let mut key_bindings = KeyBindings::default();
+
+    key_bindings.add_key(InputContext::Menu, Keys::Enter, Key::E);
+    key_bindings.add_key(InputContext::Menu, Keys::Enter, Key::E);
+    key_bindings.remove_key(InputContext::Menu, Keys::Enter, Key::SPACE);
+
+    assert_eq!(
+        key_bindings.get_keys(InputContext::Menu, Keys::Enter),
+        &[Key::ENTER, Key::E]
+    );
+}
+
+#[test]
+fn key_names_test() {
+    assert_eq!(key_from_name("Escape"), Some(Key::ESCAPE));
+    assert_eq!(key_from_name("numpad5"), Some(Key::NUMPAD5));
+    assert_eq!(key_from_name("Nope"), None);
+    assert_eq!(key_name(Key::F3), Some("F3"));
+    assert_eq!(key_name(Key::PAUSE), Some("Pause"));
+    assert_eq!(key_name(Key::UNKNOWN), None);
+}
+
+#[derive(Default)]
+struct FakeJoystickState {
+    connected: Vec<u32>,
+    axes: Vec<(u32, Axis, f32)>,
+    pressed_buttons: Vec<(u32, u32)>,
+}
+
+struct FakeJoystickSource {
+    state: Rc<RefCell<FakeJoystickState>>,
+}
+
+impl JoystickSource for FakeJoystickSource {
+    fn is_connected(&self, joystick_id: u32) -> bool {
+        self.state.borrow().connected.contains(&joystick_id)
+    }
+
+    fn axis_position(&self, joystick_id: u32, axis: Axis) -> f32 {
+        self.state
+            .borrow()
+            .axes
+            .iter()
+            .find(|(id, fake_axis, _)| *id == joystick_id && *fake_axis == axis)
+            .map_or(0.0, |(_, _, position)| *position)
+    }
+
+    fn is_button_pressed(&self, joystick_id: u32, button: u32) -> bool {
+        self.state
+            .borrow()
+            .pressed_buttons
+            .contains(&(joystick_id, button))
+    }
+}
+
+fn create_fake_gamepad() -> (Gamepad, Rc<RefCell<FakeJoystickState>>) {
+    let state = Rc::new(RefCell::new(FakeJoystickState::default()));
+    let source = FakeJoystickSource {
+        state: state.clone(),
+    };
+    (Gamepad::new(Box::new(source)), state)
+}
+
+#[test]
+fn gamepad_dead_zone_test() {
+    let (mut gamepad, state) = create_fake_gamepad();
+    state.borrow_mut().connected.push(0);
+    gamepad.update();
+
+    state.borrow_mut().axes = vec![(0, Axis::X, 15.0), (0, Axis::Y, -10.0)];
+    assert_eq!(gamepad.movement_vector().length(), 0.0);
+
+    //the rest of the range is stretched so that the edge of the dead zone moves at zero speed
+    state.borrow_mut().axes = vec![(0, Axis::X, 60.0)];
+    let movement = gamepad.movement_vector();
+    assert!((movement.get_x() - 0.5).abs() < 0.001);
+    assert_eq!(movement.get_y(), 0.0);
+
+    state.borrow_mut().axes = vec![(0, Axis::X, 100.0), (0, Axis::Y, 100.0)];
+    assert!((gamepad.movement_vector().length() - 1.0).abs() < 0.001);
+}
+
+#[test]
+fn gamepad_maps_buttons_and_directions_to_actions_test() {
+    let (mut gamepad, state) = create_fake_gamepad();
+    state.borrow_mut().connected.push(0);
+    gamepad.update();
+
+    state.borrow_mut().pressed_buttons.push((0, 0));
+    state.borrow_mut().axes = vec![(0, Axis::Y, -90.0), (0, Axis::POV_X, 100.0)];
+
+    assert!(gamepad.is_action_pressed(Keys::Enter));
+    assert!(!gamepad.is_action_pressed(Keys::Esc));
+    assert!(gamepad.is_action_pressed(Keys::Up));
+    assert!(gamepad.is_action_pressed(Keys::Right));
+    assert!(!gamepad.is_action_pressed(Keys::Down));
+    assert!(!gamepad.is_action_pressed(Keys::Left));
+}
+
+#[test]
+fn gamepad_hot_plug_test() {
+    let (mut gamepad, state) = create_fake_gamepad();
+    state.borrow_mut().pressed_buttons = vec![(0, 0), (3, 0)];
+    gamepad.update();
+    assert_eq!(gamepad.get_joystick_id(), None);
+    assert!(!gamepad.is_action_pressed(Keys::Enter));
+
+    state.borrow_mut().connected = vec![3];
+    gamepad.update();
+    assert_eq!(gamepad.get_joystick_id(), Some(3));
+    assert!(gamepad.is_action_pressed(Keys::Enter));
+
+    //sticks to the controller in use even when one with a lower id shows up
+    state.borrow_mut().connected = vec![0, 3];
+    gamepad.update();
+    assert_eq!(gamepad.get_joystick_id(), Some(3));
+
+    state.borrow_mut().connected = vec![0];
+    gamepad.update();
+    assert_eq!(gamepad.get_joystick_id(), Some(0));
+
+    state.borrow_mut().connected.clear();
+    gamepad.update();
+    assert_eq!(gamepad.get_joystick_id(), None);
+}
+
+#[test]
+fn input_tracks_focus_and_mouse_events_test() {
+    let mut input = Input::new();
+    assert!(input.has_focus());
+
+    input.on_event(&Event::LostFocus);
+    assert!(!input.has_focus());
+    input.on_event(&Event::GainedFocus);
+    assert!(input.has_focus());
+
+    input.on_event(&Event::MouseMoved { x: 10, y: 20 });
+    assert_eq!(input.get_mouse_position(), Vector::new(10.0, 20.0));
+
+    input.on_event(&Event::MouseButtonPressed {
+        button: Button::LEFT,
+        x: 30,
+        y: 40,
+    });
+    assert_eq!(input.get_mouse_position(), Vector::new(30.0, 40.0));
+}
+
+#[test]
+fn input_mouse_button_states_test() {
+    let mut input = Input::new();
+    let left_pressed = Event::MouseButtonPressed {
+        button: Button::LEFT,
+        x: 0,
+        y: 0,
+    };
+    let left_released = Event::MouseButtonReleased {
+        button: Button::LEFT,
+        x: 0,
+        y: 0,
+    };
+
+    input.on_event(&left_pressed);
+    input.update_mouse_button_states();
+    assert!(input.is_mouse_button_pressed(Button::LEFT));
+    assert!(input.mouse_button_just_pressed(Button::LEFT));
+    assert!(!input.is_mouse_button_pressed(Button::RIGHT));
+
+    input.update_mouse_button_states();
+    assert!(input.is_mouse_button_pressed(Button::LEFT));
+    assert!(!input.mouse_button_just_pressed(Button::LEFT));
+
+    input.on_event(&left_released);
+    input.update_mouse_button_states();
+    assert!(!input.is_mouse_button_pressed(Button::LEFT));
+
+    //buttons held while the window is in the background don't count
+    input.on_event(&left_pressed);
+    input.on_event(&Event::LostFocus);
+    input.update_mouse_button_states();
+    assert!(!input.is_mouse_button_pressed(Button::LEFT));
+}
+
+#[test]
+fn input_click_between_updates_is_pressed_for_one_frame_test() {
+    let mut input = Input::new();
+    input.update_mouse_button_states();
+
+    input.on_event(&Event::MouseButtonPressed {
+        button: Button::LEFT,
+        x: 0,
+        y: 0,
+    });
+    input.on_event(&Event::MouseButtonReleased {
+        button: Button::LEFT,
+        x: 0,
+        y: 0,
+    });
+    input.update_mouse_button_states();
+    assert!(input.is_mouse_button_pressed(Button::LEFT));
+    assert!(input.mouse_button_just_pressed(Button::LEFT));
+
+    input.update_mouse_button_states();
+    assert!(!input.is_mouse_button_pressed(Button::LEFT));
+    assert!(!input.mouse_button_just_pressed(Button::LEFT));
+}
+
+fn create_replay_frame(delta_time: f32, pressed_actions: Vec<Keys>) -> ReplayFrame {
+    let mut frame = ReplayFrame::default();
+    frame.delta_time = delta_time;
+    frame.pressed_actions = pressed_actions;
+    frame.has_focus = true;
+    frame
+}
+
+#[test]
+fn replay_json_round_trip_test() {
+    let mut frame = create_replay_frame(0.016, vec![Keys::Up, Keys::Enter]);
+    frame.mouse_world_position = (12.5, -3.0);
+    frame.set_mouse_buttons(&[Button::RIGHT]);
+
+    let mut replay = Replay::new(1234).with_setup(ReplaySetup {
+        simulation_rate: 30.0,
+        level_path: Some(PathBuf::from(
+            "resources/GameplayConfig/Levels/Level_1.json",
+        )),
+        skip_intro: true,
+    });
+    replay.frames = vec![frame, create_replay_frame(0.02, vec![])];
+
+    let path = env::temp_dir().join("vampire_survivors_clone_replay_test.json");
+    replay.save(&path).unwrap();
+    let loaded = Replay::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, replay);
+    assert_eq!(loaded.frames[0].get_mouse_buttons(), vec![Button::RIGHT]);
+}
+
+#[test]
+fn replay_without_setup_was_started_the_default_way_test() {
+    let path = env::temp_dir().join("vampire_survivors_clone_old_replay_test.json");
+
+    fs::write(&path, r#"{ "seed": 5, "frames": [] }"#).unwrap();
+    let loaded = Replay::load(&path);
+    fs::write(
+        &path,
+        r#"{ "seed": 5, "setup": { "simulation_rate": 0.0 }, "frames": [] }"#,
+    )
+    .unwrap();
+    let broken = Replay::load(&path);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.unwrap().setup, ReplaySetup::default());
+    assert!(broken.is_err());
+}
+
+#[test]
+fn input_plays_back_replay_test() {
+    let mut replay = Replay::new(7);
+    replay.frames = vec![
+        create_replay_frame(0.5, vec![Keys::Enter]),
+        create_replay_frame(0.25, vec![Keys::Enter, Keys::Left]),
+    ];
+
+    let mut input = Input::new();
+    input.initialize();
+    input.start_playback(replay);
+
+    //the delta time passed in is replaced by the recorded one
+    input.update(1.0);
+    assert_eq!(input.get_delta_time(), 0.5);
+    assert!(input.just_pressed(Keys::Enter));
+    assert!(!input.is_pressed(Keys::Left));
+
+    input.update(1.0);
+    assert_eq!(input.get_delta_time(), 0.25);
+    assert!(input.is_pressed(Keys::Enter));
+    assert!(!input.just_pressed(Keys::Enter));
+    assert!(input.just_pressed(Keys::Left));
+    assert_eq!(input.movement_vector().get_x(), -1.0);
+    assert!(input.is_replaying());
+}
+
+fn play_frames(input: &mut Input, frames: Vec<(f32, Vec<Keys>)>) {
+    let mut replay = Replay::new(0);
+    replay.frames = frames
+        .into_iter()
+        .map(|(delta_time, pressed_actions)| create_replay_frame(delta_time, pressed_actions))
+        .collect();
+    input.start_playback(replay);
+}
+
+#[test]
+fn input_hold_and_release_test() {
+    let mut input = Input::new();
+    input.initialize();
+    play_frames(
+        &mut input,
+        vec![
+            (0.1, vec![Keys::Enter]),
+            (0.3, vec![Keys::Enter]),
+            (0.3, vec![Keys::Enter]),
+            (0.1, vec![]),
+        ],
+    );
+
+    input.update(0.0);
+    assert!(!input.is_held_for(Keys::Enter, 0.5));
+    input.update(0.0);
+    assert!(!input.is_held_for(Keys::Enter, 0.5));
+    input.update(0.0);
+    assert!(input.is_held_for(Keys::Enter, 0.5));
+    assert!(!input.just_released(Keys::Enter));
+
+    input.update(0.0);
+    assert!(input.just_released(Keys::Enter));
+    assert!(!input.is_held_for(Keys::Enter, 0.5));
+}
+
+#[test]
+fn input_double_tap_test() {
+    let mut input = Input::new();
+    input.initialize();
+    play_frames(
+        &mut input,
+        vec![
+            (0.1, vec![Keys::Up]),
+            (0.1, vec![]),
+            (0.1, vec![Keys::Up]),
+            (0.1, vec![]),
+            (0.1, vec![Keys::Up]),
+            (0.5, vec![]),
+            (0.1, vec![Keys::Up]),
+        ],
+    );
+
+    let double_taps: Vec<bool> = (0..7)
+        .map(|_| {
+            input.update(0.0);
+            input.double_tapped(Keys::Up)
+        })
+        .collect();
+
+    //the third tap starts a new double tap instead of finishing another one, the last one comes too late
+    assert_eq!(
+        double_taps,
+        vec![false, false, true, false, false, false, false]
+    );
+}
+
+#[test]
+fn input_chord_test() {
+    let mut input = Input::new();
+    input.initialize();
+    play_frames(
+        &mut input,
+        vec![
+            (0.1, vec![Keys::Left]),
+            (0.1, vec![Keys::Left, Keys::ToggleDebug]),
+            (0.1, vec![Keys::Left, Keys::ToggleDebug]),
+        ],
+    );
+    let chord = [Keys::Left, Keys::ToggleDebug];
+
+    input.update(0.0);
+    assert!(!input.is_chord_pressed(&chord));
+    assert!(!input.chord_just_pressed(&chord));
+
+    input.update(0.0);
+    assert!(input.chord_just_pressed(&chord));
+
+    input.update(0.0);
+    assert!(input.is_chord_pressed(&chord));
+    assert!(!input.chord_just_pressed(&chord));
+}
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod Tests;
+
+use std::path::PathBuf;
+
+use crate::Objects::{FixedTimestep::FixedTimestep, Log::LogLevel};
+
+pub const USAGE: &str = "options:
+  --level <path>        level configuration to play
+  --seed <n>            seed of the random numbers
+  --skip-intro          start in the menu
+  --resolution <WxH>    size of the window, like 1280x720, instead of the saved one
+  --fullscreen          open the window in fullscreen, whatever was saved
+  --headless            simulate the level without a window and print a summary
+  --frames <n>          frames simulated by --headless
+  --tick-rate <hz>      simulation steps per second
+  --record <file>       record the run into the file
+  --replay <file>       play a recorded run
+  --profile <file>      write the timings of every frame into the file as CSV
+  --log-level <level>   off, error, warn, info or debug";
+
+/// Everything the game can be started with from the command line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LaunchOptions {
+    pub level_path: Option<PathBuf>,
+    pub seed: Option<u64>,
+    pub skip_intro: bool,
+    pub resolution: Option<(u32, u32)>,
+    pub fullscreen: bool,
+    pub headless: bool,
+    pub frames: u32,
+    pub simulation_rate: f32,
+    pub record_path: Option<PathBuf>,
+    pub replay_path: Option<PathBuf>,
+    pub profile_path: Option<PathBuf>,
+    pub log_level: LogLevel,
+}
+
+impl Default for LaunchOptions {
+    fn default() -> Self {
+        Self {
+            level_path: None,
+            seed: None,
+            skip_intro: false,
+            resolution: None,
+            fullscreen: false,
+            headless: false,
+            frames: 3600,
+            simulation_rate: FixedTimestep::DEFAULT_RATE,
+            record_path: None,
+            replay_path: None,
+            profile_path: None,
+            log_level: LogLevel::Warn,
+        }
+    }
+}
+
+impl LaunchOptions {
+    /// The arguments without the name of the program. An unknown option or a wrong value is an
+    /// error rather than being skipped, so a typo in a script doesn't go unnoticed.
+    pub fn parse(arguments: &[String]) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut arguments = arguments.iter();
+
+        while let Some(argument) = arguments.next() {
+            let mut next_value = || {
+                arguments
+                    .next()
+                    .map(|value| value.as_str())
+                    .ok_or(format!("{} needs a value", argument))
+            };
+
+            match argument.as_str() {
+                "--level" => options.level_path = Some(PathBuf::from(next_value()?)),
+                "--seed" => options.seed = Some(parse_number(argument, next_value()?)?),
+                "--skip-intro" => options.skip_intro = true,
+                "--resolution" => options.resolution = Some(parse_resolution(next_value()?)?),
+                "--fullscreen" => options.fullscreen = true,
+                "--headless" => options.headless = true,
+                "--frames" => options.frames = parse_number(argument, next_value()?)?,
+                "--tick-rate" => options.simulation_rate = parse_rate(next_value()?)?,
+                "--record" => options.record_path = Some(PathBuf::from(next_value()?)),
+                "--replay" => options.replay_path = Some(PathBuf::from(next_value()?)),
+                "--profile" => options.profile_path = Some(PathBuf::from(next_value()?)),
+                "--log-level" => {
+                    let name = next_value()?;
+                    options.log_level =
+                        LogLevel::from_name(name).ok_or(format!("unknown log level {}", name))?;
+                }
+                _ => return Err(format!("unknown option {}", argument)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(option: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| {
+        format!(
+            "{} has to be a whole number, not {}",
+            option, value
+        )
+    })
+}
+
+fn parse_rate(value: &str) -> Result<f32, String> {
+    match value.parse::<f32>() {
+        //an infinite rate would make the step 0
+        Ok(rate) if rate > 0.0 && rate.is_finite() => Ok(rate),
+        _ => Err(format!(
+            "the tick rate has to be a positive number, not {}",
+            value
+        )),
+    }
+}
+
+fn parse_resolution(value: &str) -> Result<(u32, u32), String> {
+    let error = || format!("the resolution has to look like 1280x720, not {}", value);
+    let (width, height) = value.split_once(['x', 'X']).ok_or_else(error)?;
+
+    match (width.parse::<u32>(), height.parse::<u32>()) {
+        (Ok(width), Ok(height)) if width > 0 && height > 0 => Ok((width, height)),
+        _ => Err(error()),
+    }
+}
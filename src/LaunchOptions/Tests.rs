@@ -0,0 +1,68 @@
+use std::path::PathBuf;
+
+use crate::Objects::Log::LogLevel;
+
+use super::LaunchOptions;
+
+fn parse(arguments: &[&str]) -> Result<LaunchOptions, String> {
+    let arguments: Vec<String> = arguments
+        .iter()
+        .map(|argument| argument.to_string())
+        .collect();
+    LaunchOptions::parse(&arguments)
+}
+
+#[test]
+fn no_arguments_give_defaults_test() {
+    assert_eq!(parse(&[]), Ok(LaunchOptions::default()));
+}
+
+#[test]
+fn every_option_is_read_test() {
+    let options = parse(&[
+        "--level",
+        "levels/test.json",
+        "--seed",
+        "42",
+        "--skip-intro",
+        "--resolution",
+        "800x600",
+        "--fullscreen",
+        "--headless",
+        "--frames",
+        "120",
+        "--tick-rate",
+        "30",
+        "--replay",
+        "run.json",
+        "--profile",
+        "timings.csv",
+        "--log-level",
+        "debug",
+    ])
+    .unwrap();
+
+    assert_eq!(options.level_path, Some(PathBuf::from("levels/test.json")));
+    assert_eq!(options.seed, Some(42));
+    assert!(options.skip_intro);
+    assert_eq!(options.resolution, Some((800, 600)));
+    assert!(options.fullscreen);
+    assert!(options.headless);
+    assert_eq!(options.frames, 120);
+    assert_eq!(options.simulation_rate, 30.0);
+    assert_eq!(options.replay_path, Some(PathBuf::from("run.json")));
+    assert_eq!(options.profile_path, Some(PathBuf::from("timings.csv")));
+    assert_eq!(options.log_level, LogLevel::Debug);
+}
+
+#[test]
+fn wrong_arguments_are_errors_test() {
+    assert!(parse(&["--resolution", "800"]).is_err());
+    assert!(parse(&["--resolution", "0x600"]).is_err());
+    assert!(parse(&["--seed", "-1"]).is_err());
+    assert!(parse(&["--tick-rate", "0"]).is_err());
+    assert!(parse(&["--tick-rate", "inf"]).is_err());
+    assert!(parse(&["--log-level", "loud"]).is_err());
+    assert!(parse(&["--level"]).is_err());
+    assert!(parse(&["--unknown"]).is_err());
+}
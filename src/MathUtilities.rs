@@ -1,10 +1,10 @@
+#[cfg(test)]
 mod Tests;
 
 use sfml::system::{Vector2f, Vector2u};
-use std::convert::Into;
 use std::ops::{Add, AddAssign, Mul, Sub, Neg};
 
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
 pub struct Vector {
     vector: Vector2f,
 }
@@ -31,9 +31,23 @@ impl Vector {
     }
 
     pub fn length(&self) -> f32 {
-        let length = f32::sqrt(f32::powi(self.vector.x, 2) + f32::powi(self.vector.y, 2));
+        f32::sqrt(f32::powi(self.vector.x, 2) + f32::powi(self.vector.y, 2))
+    }
+
+    pub fn dot(&self, other: Vector) -> f32 {
+        self.vector.x * other.vector.x + self.vector.y * other.vector.y
+    }
+
+    pub fn perpendicular(&self) -> Self {
+        Vector::new(-self.vector.y, self.vector.x)
+    }
 
-        length
+    pub fn rotated(&self, degrees: f32) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Vector::new(
+            self.vector.x * cos - self.vector.y * sin,
+            self.vector.x * sin + self.vector.y * cos,
+        )
     }
 
     pub fn get_x(&self) -> f32 {
@@ -44,11 +58,14 @@ impl Vector {
         self.vector.y
     }
 
+    /// Point `alpha` of the way from the start to the end, 0 giving the start and 1 the end.
+    pub fn lerp(start_point: Point, end_point: Point, alpha: f32) -> Point {
+        start_point + (end_point - start_point) * alpha
+    }
+
     pub fn distance(end_point: Point, start_point: Point) -> f32 {
         let distance_vector = end_point.vector - start_point.vector;
-        let length = f32::sqrt(f32::powi(distance_vector.x, 2) + f32::powi(distance_vector.y, 2));
-
-        length
+        f32::sqrt(f32::powi(distance_vector.x, 2) + f32::powi(distance_vector.y, 2))
     }
 }
 
@@ -99,9 +116,9 @@ impl Neg for Vector{
     type Output = Vector;
 }
 
-impl Into<Vector2f> for Vector {
-    fn into(self) -> Vector2f {
-        self.vector
+impl From<Vector> for Vector2f {
+    fn from(vector: Vector) -> Self {
+        vector.vector
     }
 }
 
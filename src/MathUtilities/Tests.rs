@@ -0,0 +1,13 @@
+use super::Vector;
+
+#[test]
+fn lerp_goes_from_start_to_end_test() {
+    let start = Vector::new(10.0, -2.0);
+    let end = Vector::new(20.0, 2.0);
+
+    let middle = Vector::lerp(start, end, 0.5);
+    assert_eq!((middle.get_x(), middle.get_y()), (15.0, 0.0));
+
+    let at_end = Vector::lerp(start, end, 1.0);
+    assert_eq!((at_end.get_x(), at_end.get_y()), (20.0, 2.0));
+}
@@ -1,3 +1,8 @@
 pub mod Interfaces;
 pub mod Animations;
+pub mod FixedTimestep;
+pub mod Log;
+pub mod Profiler;
+pub mod Random;
+pub mod Renderers;
 pub mod Timers;
\ No newline at end of file
@@ -1,9 +1,8 @@
+#[cfg(test)]
 mod Tests;
 
 use core::panic;
 use sfml::{graphics::Texture, SfBox};
-use std::clone;
-use std::marker::Copy;
 use std::{collections::HashMap, fs};
 
 pub struct Animation {
@@ -15,7 +14,7 @@ pub enum PlayersAnimations {
 }
 
 impl PlayersAnimations {
-    fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &'static str {
         match self {
             PlayersAnimations::Idle => "idle",
         }
@@ -95,8 +94,10 @@ impl AnimationPlayer {
         }
     }
 
-    pub fn get_current_animation_frame(&self) -> &Texture {
-        &self.animations.get(&self.current_animation).unwrap().frames[self.current_frame]
+    /// Nothing until the animations are loaded.
+    pub fn get_current_animation_frame(&self) -> Option<&Texture> {
+        let animation = self.animations.get(&self.current_animation)?;
+        animation.frames.get(self.current_frame).map(|frame| &**frame)
     }
 
     pub fn set_current_animation(&mut self, animation_name: String) {
@@ -113,14 +114,13 @@ impl AnimationPlayer {
         if self.current_frame >= self.current_animation_frame_count() {
             self.timer = 0.0;
             self.current_frame = 0;
-            return;
         }
     }
 
     fn current_animation_frame_count(&self) -> usize {
+        //nothing is loaded when running without graphics
         self.animations
             .get(&self.current_animation)
-            .unwrap()
-            .get_frame_count()
+            .map_or(0, |animation| animation.get_frame_count())
     }
 }
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod Tests;
+
+/// Splits the time of each frame into simulation steps of the same length, so the simulation
+/// doesn't depend on the frame rate. Time left over after the last step waits for the next frame.
+pub struct FixedTimestep {
+    step: f32,
+    max_steps_per_frame: u32,
+    accumulator: f32,
+}
+
+impl FixedTimestep {
+    pub const DEFAULT_RATE: f32 = 60.0;
+    pub const DEFAULT_MAX_STEPS_PER_FRAME: u32 = 5;
+
+    /// `rate` is the number of steps per second.
+    pub fn new(rate: f32) -> Self {
+        Self {
+            step: 1.0 / rate,
+            max_steps_per_frame: Self::DEFAULT_MAX_STEPS_PER_FRAME,
+            accumulator: 0.0,
+        }
+    }
+
+    pub fn with_max_steps_per_frame(mut self, max_steps_per_frame: u32) -> Self {
+        self.max_steps_per_frame = max_steps_per_frame;
+        self
+    }
+
+    /// Adds the time of a frame and returns how many steps to simulate. A long frame, like
+    /// while the window is dragged, is cut to the maximum number of steps and the rest of its
+    /// time is dropped instead of being caught up on later frames.
+    pub fn advance(&mut self, frame_time: f32) -> u32 {
+        self.accumulator += frame_time;
+
+        let mut steps = 0;
+        while self.accumulator >= self.step && steps < self.max_steps_per_frame {
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+
+        //only the part of a step the frame was into is kept
+        if steps == self.max_steps_per_frame {
+            self.accumulator %= self.step;
+        }
+        steps
+    }
+
+    pub fn get_step(&self) -> f32 {
+        self.step
+    }
+
+    /// How far, from 0 to 1, the time not simulated yet is into the next step.
+    pub fn get_alpha(&self) -> f32 {
+        (self.accumulator / self.step).clamp(0.0, 1.0)
+    }
+}
@@ -0,0 +1,48 @@
+use super::FixedTimestep;
+
+#[test]
+fn steps_dont_depend_on_frame_rate_test() {
+    let count_steps = |frame_time: f32, frames: u32| -> u32 {
+        let mut timestep = FixedTimestep::new(60.0);
+        (0..frames).map(|_| timestep.advance(frame_time)).sum()
+    };
+
+    //one second of frames at 30, 60 and 144 frames per second
+    assert_eq!(count_steps(1.0 / 30.0, 30), 60);
+    assert_eq!(count_steps(1.0 / 60.0, 60), 60);
+    assert!((59..=60).contains(&count_steps(1.0 / 144.0, 144)));
+}
+
+#[test]
+fn leftover_time_gives_alpha_test() {
+    let mut timestep = FixedTimestep::new(10.0);
+
+    assert_eq!(timestep.advance(0.25), 2);
+    assert!((timestep.get_alpha() - 0.5).abs() < 1e-4);
+
+    assert_eq!(timestep.advance(0.05), 1);
+    assert!(timestep.get_alpha() < 1e-4);
+}
+
+#[test]
+fn long_frame_is_clamped_test() {
+    let mut timestep = FixedTimestep::new(60.0);
+
+    assert_eq!(
+        timestep.advance(2.0),
+        FixedTimestep::DEFAULT_MAX_STEPS_PER_FRAME
+    );
+    //the dropped time isn't caught up on afterwards
+    assert!(timestep.advance(1.0 / 60.0) <= 2);
+}
+
+#[test]
+fn frame_after_clamp_runs_its_own_steps_test() {
+    let mut timestep = FixedTimestep::new(10.0).with_max_steps_per_frame(3);
+
+    assert_eq!(timestep.advance(1.05), 3);
+    assert!((timestep.get_alpha() - 0.5).abs() < 1e-3);
+
+    assert_eq!(timestep.advance(0.1), 1);
+    assert!((timestep.get_alpha() - 0.5).abs() < 1e-3);
+}
@@ -1,11 +1,40 @@
-use sfml::graphics::RenderWindow;
+use sfml::graphics::{Color, Texture};
+
+use crate::MathUtilities::{Point, Vector};
 
 pub trait Updatable {
     fn update(&mut self, delta_time: f32);
 }
 
 pub trait Drawable {
-    fn draw(&mut self, window: &mut RenderWindow);
+    /// `alpha` is how far, from 0 to 1, the time drawn is between the last two simulation steps.
+    fn draw(&mut self, renderer: &mut dyn Renderer, alpha: f32);
+}
+
+/// Everything the game draws goes through these commands, positions and sizes are in pixels.
+pub trait Renderer {
+    fn get_size(&self) -> Vector;
+
+    /// Texture stretched over the rectangle centered on the position. The scale is applied
+    /// around the center, so a negative one mirrors the texture.
+    fn draw_sprite(&mut self, texture: &Texture, center: Point, size: Vector, scale: Vector);
+    fn draw_rectangle(&mut self, position: Point, size: Vector, color: Color);
+    /// Outline around the outside of the rectangle.
+    fn draw_rectangle_outline(&mut self, position: Point, size: Vector, thickness: f32, color: Color);
+    fn draw_circle(&mut self, center: Point, radius: f32, color: Color);
+    /// Filled convex polygon, with its points in order around it.
+    fn draw_polygon(&mut self, points: &[Point], color: Color);
+    fn draw_line_strip(&mut self, points: &[Point], color: Color);
+    /// Text in the font of the game, starting at its top left corner.
+    fn draw_text(&mut self, text: &str, character_size: u32, position: Point, color: Color);
+    fn measure_text(&self, text: &str, character_size: u32) -> Vector;
+
+    /// Everything drawn afterwards is moved by the offset.
+    fn set_offset(&mut self, offset: Vector);
+    fn get_offset(&self) -> Vector;
+
+    /// What `draw` draws is put together first and then blended over what is already drawn.
+    fn draw_faded(&mut self, opacity: f32, draw: &mut dyn FnMut(&mut dyn Renderer));
 }
 
 pub trait Initializable {
@@ -0,0 +1,69 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl LogLevel {
+    const ALL: [LogLevel; 5] = [
+        LogLevel::Off,
+        LogLevel::Error,
+        LogLevel::Warn,
+        LogLevel::Info,
+        LogLevel::Debug,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.get_name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            LogLevel::Off => "off",
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+        }
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Warn as u8);
+
+/// Messages less important than the level aren't printed.
+pub fn set_level(level: LogLevel) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn get_level() -> LogLevel {
+    LogLevel::ALL[LEVEL.load(Ordering::Relaxed) as usize]
+}
+
+pub fn error(message: &str) {
+    log(LogLevel::Error, message);
+}
+
+pub fn warn(message: &str) {
+    log(LogLevel::Warn, message);
+}
+
+pub fn info(message: &str) {
+    log(LogLevel::Info, message);
+}
+
+pub fn debug(message: &str) {
+    log(LogLevel::Debug, message);
+}
+
+fn log(level: LogLevel, message: &str) {
+    if level <= get_level() {
+        eprintln!("[{}] {}", level.get_name(), message);
+    }
+}
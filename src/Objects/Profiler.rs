@@ -0,0 +1,253 @@
+pub mod PerformanceHud;
+#[cfg(test)]
+mod Tests;
+
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use super::Log;
+
+/// Parts of a frame whose time is measured.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Update,
+    UpdatePlayer,
+    UpdateWeapons,
+    UpdateEnemies,
+    UpdateWord,
+    Draw,
+    DrawPlayer,
+    DrawEnemies,
+    DrawWeapons,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 9] = [
+        Phase::Update,
+        Phase::UpdatePlayer,
+        Phase::UpdateWeapons,
+        Phase::UpdateEnemies,
+        Phase::UpdateWord,
+        Phase::Draw,
+        Phase::DrawPlayer,
+        Phase::DrawEnemies,
+        Phase::DrawWeapons,
+    ];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Phase::Update => "update",
+            Phase::UpdatePlayer => "update_player",
+            Phase::UpdateWeapons => "update_weapons",
+            Phase::UpdateEnemies => "update_enemies",
+            Phase::UpdateWord => "update_word",
+            Phase::Draw => "draw",
+            Phase::DrawPlayer => "draw_player",
+            Phase::DrawEnemies => "draw_enemies",
+            Phase::DrawWeapons => "draw_weapons",
+        }
+    }
+}
+
+/// Numbers counted during a frame, next to the timings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Counter {
+    Entities,
+    Enemies,
+    Missiles,
+    //pairs the broad phase handed to the narrow phase
+    CheckedPairs,
+    CollidingPairs,
+}
+
+impl Counter {
+    pub const ALL: [Counter; 5] = [
+        Counter::Entities,
+        Counter::Enemies,
+        Counter::Missiles,
+        Counter::CheckedPairs,
+        Counter::CollidingPairs,
+    ];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Counter::Entities => "entities",
+            Counter::Enemies => "enemies",
+            Counter::Missiles => "missiles",
+            Counter::CheckedPairs => "checked_pairs",
+            Counter::CollidingPairs => "colliding_pairs",
+        }
+    }
+}
+
+/// Everything measured during one frame, the times are in milliseconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FrameTimings {
+    pub frame_time: f32,
+    pub phase_times: [f32; Phase::ALL.len()],
+    pub counters: [usize; Counter::ALL.len()],
+}
+
+impl FrameTimings {
+    pub fn get_phase_time(&self, phase: Phase) -> f32 {
+        self.phase_times[phase as usize]
+    }
+
+    pub fn get_counter(&self, counter: Counter) -> usize {
+        self.counters[counter as usize]
+    }
+
+    fn get_csv_header() -> String {
+        let mut columns = vec!["frame", "frame_time"];
+        columns.extend(Phase::ALL.iter().map(|phase| phase.get_name()));
+        columns.extend(Counter::ALL.iter().map(|counter| counter.get_name()));
+        columns.join(",")
+    }
+
+    fn to_csv_row(self, frame_index: u64) -> String {
+        let mut columns = vec![frame_index.to_string(), format!("{:.4}", self.frame_time)];
+        columns.extend(self.phase_times.iter().map(|time| format!("{:.4}", time)));
+        columns.extend(self.counters.iter().map(|count| count.to_string()));
+        columns.join(",")
+    }
+}
+
+/// Collects the timings of the current frame and keeps those of the last frames.
+pub struct Profiler {
+    current: FrameTimings,
+    history: VecDeque<FrameTimings>,
+    frame_index: u64,
+    csv: Option<BufWriter<File>>,
+}
+
+impl Profiler {
+    pub const HISTORY_LENGTH: usize = 120;
+
+    pub fn new() -> Self {
+        Self {
+            current: FrameTimings::default(),
+            history: VecDeque::with_capacity(Self::HISTORY_LENGTH),
+            frame_index: 0,
+            csv: None,
+        }
+    }
+
+    /// A phase measured more than once in a frame, like during several simulation steps,
+    /// adds up.
+    pub fn add_phase_time(&mut self, phase: Phase, duration: Duration) {
+        self.current.phase_times[phase as usize] += duration.as_secs_f32() * 1000.0;
+    }
+
+    pub fn set_counter(&mut self, counter: Counter, value: usize) {
+        self.current.counters[counter as usize] = value;
+    }
+
+    /// `frame_time` is in seconds. The times of the next frame start from zero, the counters
+    /// keep their values until they are counted again, a frame without a simulation step
+    /// doesn't count anything.
+    pub fn end_frame(&mut self, frame_time: f32) {
+        let next = FrameTimings {
+            counters: self.current.counters,
+            ..FrameTimings::default()
+        };
+        let mut frame = std::mem::replace(&mut self.current, next);
+        frame.frame_time = frame_time * 1000.0;
+
+        self.write_csv_row(&frame);
+        self.frame_index += 1;
+
+        if self.history.len() == Self::HISTORY_LENGTH {
+            self.history.pop_front();
+        }
+        self.history.push_back(frame);
+    }
+
+    /// Oldest first.
+    pub fn get_history(&self) -> &VecDeque<FrameTimings> {
+        &self.history
+    }
+
+    /// Averaged over the history, 0 before the first frame.
+    pub fn get_average(&self, get_value: impl Fn(&FrameTimings) -> f32) -> f32 {
+        if self.history.is_empty() {
+            return 0.0;
+        }
+        self.history.iter().map(get_value).sum::<f32>() / self.history.len() as f32
+    }
+
+    pub fn get_fps(&self) -> f32 {
+        let average_frame_time = self.get_average(|frame| frame.frame_time);
+        if average_frame_time > 0.0 {
+            1000.0 / average_frame_time
+        } else {
+            0.0
+        }
+    }
+
+    pub fn get_last_frame(&self) -> FrameTimings {
+        self.history.back().copied().unwrap_or_default()
+    }
+
+    /// Every following frame is written to the file as a row, until `stop_csv`.
+    pub fn start_csv(&mut self, path: &Path) -> io::Result<()> {
+        let mut csv = BufWriter::new(File::create(path)?);
+        writeln!(csv, "{}", FrameTimings::get_csv_header())?;
+        self.csv = Some(csv);
+        Ok(())
+    }
+
+    pub fn is_writing_csv(&self) -> bool {
+        self.csv.is_some()
+    }
+
+    pub fn stop_csv(&mut self) -> io::Result<()> {
+        match self.csv.take() {
+            Some(mut csv) => csv.flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn write_csv_row(&mut self, frame: &FrameTimings) {
+        let csv = match self.csv.as_mut() {
+            Some(csv) => csv,
+            None => return,
+        };
+
+        if let Err(error) = writeln!(csv, "{}", frame.to_csv_row(self.frame_index)) {
+            //one error is enough, it would be printed again every frame
+            Log::error(&format!("couldn't write the timings: {}", error));
+            self.csv = None;
+        }
+    }
+}
+
+thread_local! {
+    static PROFILER: RefCell<Profiler> = RefCell::new(Profiler::new());
+}
+
+/// Gives the profiler of the thread, which the game and its measurements share.
+pub fn with_profiler<R>(function: impl FnOnce(&mut Profiler) -> R) -> R {
+    PROFILER.with(|profiler| function(&mut profiler.borrow_mut()))
+}
+
+/// Runs the function and adds the time it took to the phase.
+pub fn measure<R>(phase: Phase, function: impl FnOnce() -> R) -> R {
+    let start = Instant::now();
+    let result = function();
+    with_profiler(|profiler| profiler.add_phase_time(phase, start.elapsed()));
+    result
+}
+
+pub fn set_counter(counter: Counter, value: usize) {
+    with_profiler(|profiler| profiler.set_counter(counter, value));
+}
+
+pub fn end_frame(frame_time: f32) {
+    with_profiler(|profiler| profiler.end_frame(frame_time));
+}
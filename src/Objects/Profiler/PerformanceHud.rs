@@ -0,0 +1,117 @@
+use sfml::graphics::Color;
+
+use crate::{
+    MathUtilities::{Point, Vector},
+    Objects::Interfaces::{Drawable, Renderer},
+};
+
+use super::{with_profiler, Counter, Phase, Profiler};
+
+const CHARACTER_SIZE: u32 = 14;
+const LINE_HEIGHT: f32 = 18.0;
+const WIDTH: f32 = 300.0;
+const MARGIN: f32 = 10.0;
+const GRAPH_HEIGHT: f32 = 60.0;
+//a frame this long reaches the top of the graph
+const GRAPH_MAX_FRAME_TIME: f32 = 1000.0 / 30.0;
+const TARGET_FRAME_TIME: f32 = 1000.0 / 60.0;
+
+/// Overlay with the frame rate, the times of the last frames and what was counted in them.
+pub struct PerformanceHud {
+    is_visible: bool,
+}
+
+impl PerformanceHud {
+    pub fn new() -> Self {
+        Self { is_visible: false }
+    }
+
+    pub fn toggle(&mut self) {
+        self.is_visible = !self.is_visible;
+    }
+
+    fn get_lines(profiler: &Profiler) -> Vec<String> {
+        let mut lines = vec![format!(
+            "FPS: {:.0} ({:.2} ms)",
+            profiler.get_fps(),
+            profiler.get_average(|frame| frame.frame_time)
+        )];
+
+        //averaged, a single frame jumps around too much to be read
+        lines.extend(Phase::ALL.iter().map(|phase| {
+            let time = profiler.get_average(|frame| frame.get_phase_time(*phase));
+            format!("{}: {:.2} ms", phase.get_name(), time)
+        }));
+
+        let last_frame = profiler.get_last_frame();
+        lines.extend(Counter::ALL.iter().map(|counter| {
+            format!(
+                "{}: {}",
+                counter.get_name(),
+                last_frame.get_counter(*counter)
+            )
+        }));
+
+        lines
+    }
+
+    fn draw_graph(profiler: &Profiler, renderer: &mut dyn Renderer, position: Point) {
+        let size = Vector::new(WIDTH - 2.0 * MARGIN, GRAPH_HEIGHT);
+        renderer.draw_rectangle_outline(position, size, 1.0, Color::WHITE);
+
+        let target_height = size.get_y() * TARGET_FRAME_TIME / GRAPH_MAX_FRAME_TIME;
+        let target_position = position + Vector::new(0.0, size.get_y() - target_height);
+        renderer.draw_rectangle(
+            target_position,
+            Vector::new(size.get_x(), 1.0),
+            Color::GREEN,
+        );
+
+        let spacing = size.get_x() / (Profiler::HISTORY_LENGTH - 1) as f32;
+        let points: Vec<Point> = profiler
+            .get_history()
+            .iter()
+            .enumerate()
+            .map(|(i, frame)| {
+                let height = size.get_y() * (frame.frame_time / GRAPH_MAX_FRAME_TIME).min(1.0);
+                position + Vector::new(i as f32 * spacing, size.get_y() - height)
+            })
+            .collect();
+        if points.len() > 1 {
+            renderer.draw_line_strip(&points, Color::YELLOW);
+        }
+    }
+
+    fn draw_profile(profiler: &Profiler, renderer: &mut dyn Renderer) {
+        let lines = Self::get_lines(profiler);
+        let height = 3.0 * MARGIN + GRAPH_HEIGHT + lines.len() as f32 * LINE_HEIGHT;
+        let position = Vector::new(renderer.get_size().get_x() - WIDTH - MARGIN, MARGIN);
+        renderer.draw_rectangle(
+            position,
+            Vector::new(WIDTH, height),
+            Color::rgba(0, 0, 0, 180),
+        );
+
+        let mut line_position = position + Vector::new(MARGIN, MARGIN);
+        for line in &lines {
+            renderer.draw_text(line, CHARACTER_SIZE, line_position, Color::WHITE);
+            line_position += Vector::new(0.0, LINE_HEIGHT);
+        }
+
+        Self::draw_graph(profiler, renderer, line_position + Vector::new(0.0, MARGIN));
+    }
+}
+
+impl Drawable for PerformanceHud {
+    fn draw(&mut self, renderer: &mut dyn Renderer, _alpha: f32) {
+        if !self.is_visible {
+            return;
+        }
+
+        //drawn over everything, whatever the states moved the drawing by
+        let offset = renderer.get_offset();
+        renderer.set_offset(Vector::default());
+        with_profiler(|profiler| Self::draw_profile(profiler, renderer));
+        renderer.set_offset(offset);
+    }
+}
@@ -0,0 +1,102 @@
+use std::{env, fs, time::Duration};
+
+use crate::{
+    MathUtilities::Vector,
+    Objects::{Interfaces::Drawable, Renderers::RecordingRenderer::RecordingRenderer},
+};
+
+use super::{
+    end_frame, measure, set_counter, with_profiler, Counter, PerformanceHud::PerformanceHud, Phase,
+    Profiler,
+};
+
+#[test]
+fn phases_add_up_until_frame_ends_test() {
+    let mut profiler = Profiler::new();
+
+    profiler.add_phase_time(Phase::UpdateEnemies, Duration::from_millis(2));
+    profiler.add_phase_time(Phase::UpdateEnemies, Duration::from_millis(3));
+    profiler.set_counter(Counter::Enemies, 40);
+    profiler.end_frame(0.02);
+
+    let frame = profiler.get_last_frame();
+    assert!((frame.get_phase_time(Phase::UpdateEnemies) - 5.0).abs() < 1e-3);
+    assert!((frame.frame_time - 20.0).abs() < 1e-3);
+    assert_eq!(frame.get_counter(Counter::Enemies), 40);
+
+    //the times of the next frame start from zero, the counters are kept
+    profiler.end_frame(0.02);
+    assert_eq!(
+        profiler
+            .get_last_frame()
+            .get_phase_time(Phase::UpdateEnemies),
+        0.0
+    );
+    assert_eq!(profiler.get_last_frame().get_counter(Counter::Enemies), 40);
+    assert!((profiler.get_fps() - 50.0).abs() < 1e-2);
+}
+
+#[test]
+fn history_keeps_last_frames_test() {
+    let mut profiler = Profiler::new();
+    assert_eq!(profiler.get_fps(), 0.0);
+
+    for i in 0..Profiler::HISTORY_LENGTH + 10 {
+        profiler.end_frame(i as f32 / 1000.0);
+    }
+
+    assert_eq!(profiler.get_history().len(), Profiler::HISTORY_LENGTH);
+    assert!((profiler.get_history()[0].frame_time - 10.0).abs() < 1e-3);
+}
+
+#[test]
+fn timings_are_written_to_csv_test() {
+    let path = env::temp_dir().join("vampire_survivors_clone_profiler_test.csv");
+    let mut profiler = Profiler::new();
+
+    profiler.start_csv(&path).unwrap();
+    assert!(profiler.is_writing_csv());
+    profiler.set_counter(Counter::CheckedPairs, 12);
+    profiler.end_frame(0.016);
+    profiler.end_frame(0.017);
+    profiler.stop_csv().unwrap();
+    assert!(!profiler.is_writing_csv());
+    //frames after stopping aren't written
+    profiler.end_frame(0.018);
+
+    let csv = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("frame,frame_time,update,update_player"));
+    assert!(lines[0].ends_with("checked_pairs,colliding_pairs"));
+    assert!(lines[1].starts_with("0,16.0000,"));
+    assert!(lines[1].ends_with(",12,0"));
+    assert!(lines[2].starts_with("1,17.0000,"));
+}
+
+#[test]
+fn hud_shows_measured_phases_test() {
+    let mut hud = PerformanceHud::new();
+    let mut renderer = RecordingRenderer::new(Vector::new(800.0, 600.0));
+
+    hud.draw(&mut renderer, 1.0);
+    assert!(renderer.get_commands().is_empty());
+
+    //the profiler is per thread, so other tests don't add to it
+    with_profiler(|profiler| *profiler = Profiler::new());
+    assert_eq!(measure(Phase::UpdateWord, || 7), 7);
+    end_frame(0.02);
+    set_counter(Counter::CollidingPairs, 3);
+    end_frame(0.02);
+
+    hud.toggle();
+    hud.draw(&mut renderer, 1.0);
+
+    let texts = renderer.get_texts();
+    assert_eq!(texts[0], "FPS: 50 (20.00 ms)");
+    assert!(texts.iter().any(|text| text.starts_with("update_word: ")));
+    //counters come from the last frame
+    assert!(texts.contains(&"colliding_pairs: 3"));
+}
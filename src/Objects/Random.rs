@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod Tests;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+
+static SEED: AtomicU64 = AtomicU64::new(0);
+static NEXT_STREAM: AtomicU64 = AtomicU64::new(0);
+
+/// Seed of every random generator made from now on, a replay sets the seed it was recorded with.
+pub fn set_seed(seed: u64) {
+    SEED.store(seed, Ordering::Relaxed);
+    NEXT_STREAM.store(0, Ordering::Relaxed);
+}
+
+pub fn get_seed() -> u64 {
+    SEED.load(Ordering::Relaxed)
+}
+
+pub fn random_seed() -> u64 {
+    thread_rng().gen()
+}
+
+/// Generators are numbered in the order they are made, so a run making them in the same order
+/// gets the same numbers.
+pub fn create_rng() -> StdRng {
+    let stream = NEXT_STREAM.fetch_add(1, Ordering::Relaxed);
+    StdRng::seed_from_u64(get_seed().wrapping_add(stream))
+}
@@ -0,0 +1,30 @@
+use rand::Rng;
+
+use super::{create_rng, set_seed};
+
+#[test]
+fn same_seed_gives_same_numbers_test() {
+    let draw = || -> Vec<u32> {
+        let mut first_rng = create_rng();
+        let mut second_rng = create_rng();
+        (0..8)
+            .map(|i| {
+                if i % 2 == 0 {
+                    first_rng.gen()
+                } else {
+                    second_rng.gen()
+                }
+            })
+            .collect()
+    };
+
+    set_seed(42);
+    let first_run = draw();
+    set_seed(42);
+    let second_run = draw();
+    set_seed(43);
+    let other_seed_run = draw();
+
+    assert_eq!(first_run, second_run);
+    assert_ne!(first_run, other_seed_run);
+}
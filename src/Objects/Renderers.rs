@@ -0,0 +1,3 @@
+#[cfg(test)]
+pub mod RecordingRenderer;
+pub mod SfmlRenderer;
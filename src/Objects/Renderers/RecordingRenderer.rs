@@ -0,0 +1,183 @@
+use sfml::graphics::{Color, Texture};
+
+use crate::{
+    MathUtilities::{Point, Vector},
+    Objects::Interfaces::Renderer,
+};
+
+/// One call made to a `Renderer`, textures aren't kept.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DrawCommand {
+    Sprite {
+        center: Point,
+        size: Vector,
+        scale: Vector,
+    },
+    Rectangle {
+        position: Point,
+        size: Vector,
+        color: Color,
+    },
+    RectangleOutline {
+        position: Point,
+        size: Vector,
+        thickness: f32,
+        color: Color,
+    },
+    Circle {
+        center: Point,
+        radius: f32,
+        color: Color,
+    },
+    Polygon {
+        points: Vec<Point>,
+        color: Color,
+    },
+    LineStrip {
+        points: Vec<Point>,
+        color: Color,
+    },
+    Text {
+        text: String,
+        character_size: u32,
+        position: Point,
+        color: Color,
+    },
+    SetOffset(Vector),
+    Faded {
+        opacity: f32,
+        commands: Vec<DrawCommand>,
+    },
+}
+
+/// Keeps the commands instead of drawing them, so what gets drawn can be checked without a
+/// window or a graphics card.
+pub struct RecordingRenderer {
+    size: Vector,
+    offset: Vector,
+    commands: Vec<DrawCommand>,
+}
+
+impl RecordingRenderer {
+    /// Width of a character, relative to the character size, when measuring text.
+    pub const CHARACTER_WIDTH: f32 = 0.5;
+
+    pub fn new(size: Vector) -> Self {
+        Self {
+            size,
+            offset: Vector::default(),
+            commands: vec![],
+        }
+    }
+
+    pub fn get_commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+
+    /// Strings of the text commands, in the order they were drawn.
+    pub fn get_texts(&self) -> Vec<&str> {
+        self.commands
+            .iter()
+            .filter_map(|command| match command {
+                DrawCommand::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Renderer for RecordingRenderer {
+    fn get_size(&self) -> Vector {
+        self.size
+    }
+
+    fn draw_sprite(&mut self, _texture: &Texture, center: Point, size: Vector, scale: Vector) {
+        self.commands.push(DrawCommand::Sprite {
+            center,
+            size,
+            scale,
+        });
+    }
+
+    fn draw_rectangle(&mut self, position: Point, size: Vector, color: Color) {
+        self.commands.push(DrawCommand::Rectangle {
+            position,
+            size,
+            color,
+        });
+    }
+
+    fn draw_rectangle_outline(
+        &mut self,
+        position: Point,
+        size: Vector,
+        thickness: f32,
+        color: Color,
+    ) {
+        self.commands.push(DrawCommand::RectangleOutline {
+            position,
+            size,
+            thickness,
+            color,
+        });
+    }
+
+    fn draw_circle(&mut self, center: Point, radius: f32, color: Color) {
+        self.commands.push(DrawCommand::Circle {
+            center,
+            radius,
+            color,
+        });
+    }
+
+    fn draw_polygon(&mut self, points: &[Point], color: Color) {
+        self.commands.push(DrawCommand::Polygon {
+            points: points.to_vec(),
+            color,
+        });
+    }
+
+    fn draw_line_strip(&mut self, points: &[Point], color: Color) {
+        self.commands.push(DrawCommand::LineStrip {
+            points: points.to_vec(),
+            color,
+        });
+    }
+
+    fn draw_text(&mut self, text: &str, character_size: u32, position: Point, color: Color) {
+        self.commands.push(DrawCommand::Text {
+            text: String::from(text),
+            character_size,
+            position,
+            color,
+        });
+    }
+
+    fn measure_text(&self, text: &str, character_size: u32) -> Vector {
+        let character_size = character_size as f32;
+        Vector::new(
+            text.chars().count() as f32 * character_size * Self::CHARACTER_WIDTH,
+            character_size,
+        )
+    }
+
+    fn set_offset(&mut self, offset: Vector) {
+        self.offset = offset;
+        self.commands.push(DrawCommand::SetOffset(offset));
+    }
+
+    fn get_offset(&self) -> Vector {
+        self.offset
+    }
+
+    fn draw_faded(&mut self, opacity: f32, draw: &mut dyn FnMut(&mut dyn Renderer)) {
+        let mut faded = RecordingRenderer::new(self.size);
+        faded.offset = self.offset;
+        draw(&mut faded);
+
+        self.commands.push(DrawCommand::Faded {
+            opacity,
+            commands: faded.commands,
+        });
+    }
+}
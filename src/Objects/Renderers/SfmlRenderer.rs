@@ -0,0 +1,178 @@
+use sfml::{
+    graphics::{
+        CircleShape, Color, Font, PrimitiveType, RectangleShape, RenderStates, RenderTarget,
+        RenderTexture, Shape, Sprite, Text, Texture, Transformable, Vertex, View,
+    },
+    SfBox,
+};
+
+use crate::{
+    MathUtilities::{Point, Vector},
+    Objects::Interfaces::Renderer,
+};
+
+pub const FONT_PATH: &str = "resources/Fonts/gomarice_no_continue.ttf";
+
+/// What the renderer keeps between frames.
+pub struct RenderResources {
+    font: SfBox<Font>,
+    //what is drawn faded is put together here before being blended over the target
+    blend_texture: Option<RenderTexture>,
+}
+
+impl RenderResources {
+    pub fn new() -> Self {
+        Self {
+            font: Font::from_file(FONT_PATH).unwrap(),
+            blend_texture: None,
+        }
+    }
+}
+
+pub struct SfmlRenderer<'a> {
+    target: &'a mut dyn RenderTarget,
+    resources: &'a mut RenderResources,
+
+    base_view: SfBox<View>,
+    offset: Vector,
+}
+
+impl<'a> SfmlRenderer<'a> {
+    pub fn new(target: &'a mut dyn RenderTarget, resources: &'a mut RenderResources) -> Self {
+        Self {
+            base_view: target.view().to_owned(),
+            target,
+            resources,
+            offset: Vector::default(),
+        }
+    }
+
+    fn create_text<'font>(font: &'font Font, text: &str, character_size: u32) -> Text<'font> {
+        Text::new(text, font, character_size)
+    }
+
+    fn take_blend_texture(&mut self) -> Option<RenderTexture> {
+        let size = self.target.size();
+        match self.resources.blend_texture.take() {
+            Some(blend_texture) if blend_texture.size() == size => Some(blend_texture),
+            _ => RenderTexture::new(size.x, size.y, false),
+        }
+    }
+}
+
+impl<'a> Renderer for SfmlRenderer<'a> {
+    fn get_size(&self) -> Vector {
+        Vector::from_Vector2u(self.target.size())
+    }
+
+    fn draw_sprite(&mut self, texture: &Texture, center: Point, size: Vector, scale: Vector) {
+        let mut sprite = RectangleShape::with_size(size.into());
+        sprite.set_texture(texture, false);
+        sprite.set_position(center);
+        sprite.set_origin(size * 0.5);
+        sprite.set_scale(scale);
+        self.target.draw(&sprite);
+    }
+
+    fn draw_rectangle(&mut self, position: Point, size: Vector, color: Color) {
+        let mut rectangle = RectangleShape::with_size(size.into());
+        rectangle.set_position(position);
+        rectangle.set_fill_color(color);
+        self.target.draw(&rectangle);
+    }
+
+    fn draw_rectangle_outline(
+        &mut self,
+        position: Point,
+        size: Vector,
+        thickness: f32,
+        color: Color,
+    ) {
+        let mut rectangle = RectangleShape::with_size(size.into());
+        rectangle.set_position(position);
+        rectangle.set_fill_color(Color::TRANSPARENT);
+        rectangle.set_outline_color(color);
+        rectangle.set_outline_thickness(thickness);
+        self.target.draw(&rectangle);
+    }
+
+    fn draw_circle(&mut self, center: Point, radius: f32, color: Color) {
+        let mut circle = CircleShape::new(radius, 100);
+        circle.set_position(center);
+        circle.set_origin(Vector::new(radius, radius));
+        circle.set_fill_color(color);
+        self.target.draw(&circle);
+    }
+
+    fn draw_polygon(&mut self, points: &[Point], color: Color) {
+        let vertices: Vec<Vertex> = points
+            .iter()
+            .map(|point| Vertex::with_pos_color((*point).into(), color))
+            .collect();
+        self.target
+            .draw_primitives(&vertices, PrimitiveType::TRIANGLE_FAN, &RenderStates::DEFAULT);
+    }
+
+    fn draw_line_strip(&mut self, points: &[Point], color: Color) {
+        let vertices: Vec<Vertex> = points
+            .iter()
+            .map(|point| Vertex::with_pos_color((*point).into(), color))
+            .collect();
+        self.target
+            .draw_primitives(&vertices, PrimitiveType::LINE_STRIP, &RenderStates::DEFAULT);
+    }
+
+    fn draw_text(&mut self, text: &str, character_size: u32, position: Point, color: Color) {
+        let mut text = Self::create_text(&self.resources.font, text, character_size);
+        text.set_position(position);
+        text.set_fill_color(color);
+        self.target.draw(&text);
+    }
+
+    fn measure_text(&self, text: &str, character_size: u32) -> Vector {
+        let bounds = Self::create_text(&self.resources.font, text, character_size).global_bounds();
+        Vector::new(bounds.left + bounds.width, bounds.top + bounds.height)
+    }
+
+    fn set_offset(&mut self, offset: Vector) {
+        let mut view = self.base_view.clone();
+        view.move_(-offset);
+        self.target.set_view(&view);
+        self.offset = offset;
+    }
+
+    fn get_offset(&self) -> Vector {
+        self.offset
+    }
+
+    fn draw_faded(&mut self, opacity: f32, draw: &mut dyn FnMut(&mut dyn Renderer)) {
+        //without a texture to blend through it's drawn as it is
+        let mut blend_texture = match self.take_blend_texture() {
+            Some(blend_texture) => blend_texture,
+            None => return draw(self),
+        };
+
+        blend_texture.clear(Color::TRANSPARENT);
+        blend_texture.set_view(self.target.view());
+        draw(&mut SfmlRenderer::new(&mut blend_texture, self.resources));
+        blend_texture.display();
+
+        //the texture already is what the view sees
+        let view = self.target.view().to_owned();
+        let default_view = self.target.default_view().to_owned();
+        self.target.set_view(&default_view);
+        {
+            let mut sprite = Sprite::with_texture(blend_texture.texture());
+            sprite.set_color(Color::rgba(
+                255,
+                255,
+                255,
+                (opacity.clamp(0.0, 1.0) * 255.0) as u8,
+            ));
+            self.target.draw(&sprite);
+        }
+        self.target.set_view(&view);
+
+        self.resources.blend_texture = Some(blend_texture);
+    }
+}
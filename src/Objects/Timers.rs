@@ -15,7 +15,7 @@ pub struct BasicTimer{
 
 impl BasicTimer{
     pub fn new(duration: f32)->Self{
-        Self { duration: duration, remaining_time: duration, active: false }
+        Self { duration, remaining_time: duration, active: false }
     }
 }
 
@@ -1,54 +1,241 @@
+use std::{io, path::{Path, PathBuf}};
+
 use sfml::{
-    graphics::{RenderWindow, RenderTarget, Color},
+    audio::listener,
+    graphics::{RenderWindow, RenderTarget, Color, FloatRect, View},
     system::Clock,
     window::{ContextSettings, Event, Style, VideoMode},
 };
 
 mod ProgramControllFlow;
-use ProgramControllFlow::States;
-use crate::{ControllFlow::StateMachine, Objects::Interfaces::{Updatable, Drawable, Initializable}, InputSystem::{Input, InputConsumer}};
+use ProgramControllFlow::{ProgramContext, States};
+pub use ProgramControllFlow::{HeadlessSimulation, DEFAULT_LEVEL_PATH, check_level};
+use crate::{ControllFlow::StateMachine, Objects::{Interfaces::{Updatable, Drawable, Initializable}, FixedTimestep::FixedTimestep, Log, Profiler::{self, Phase, PerformanceHud::PerformanceHud}, Random, Renderers::SfmlRenderer::{RenderResources, SfmlRenderer}}, InputSystem::{Input, InputConsumer, Keys, Replay::{Replay, ReplaySetup}}, Settings::{Settings, WindowOverrides}};
+
+//the timings captured with the debug chord, profiling from the command line picks its own file
+const TIMINGS_CAPTURE_PATH: &str = "timings.csv";
+const TIMINGS_CAPTURE_CHORD: [Keys; 2] = [Keys::ToggleDebug, Keys::TogglePerformanceHud];
+//a longer frame is simulated slowed down instead of being caught up on, whatever the rate
+const MAX_SIMULATED_FRAME_TIME: f32 = FixedTimestep::DEFAULT_MAX_STEPS_PER_FRAME as f32 / FixedTimestep::DEFAULT_RATE;
 
 pub struct Program {
     window: RenderWindow,
     timer: Clock,
+    timestep: FixedTimestep,
+    //how the run was started, kept for the replays
+    simulation_rate: f32,
+    has_skipped_intro: bool,
+    render_resources: RenderResources,
     states: StateMachine<States>,
     input: Input,
+    performance_hud: PerformanceHud,
+
+    context: ProgramContext,
+    //what the window and the input were last set up with
+    settings: Settings,
+    window_overrides: WindowOverrides,
+    settings_path: PathBuf,
 }
 
 impl Program {
-    pub fn new() -> Self {
+    /// Opens the window with the settings, later changes are saved to the file.
+    pub fn new(settings: Settings, settings_path: &Path, level_path: &Path, window_overrides: WindowOverrides) -> Self {
+        let context = ProgramContext::new(settings.clone(), level_path.to_path_buf());
+
         Self {
-            window: Self::create_window(),
+            window: Self::create_window(&settings, &window_overrides),
             timer: Clock::default(),
-            states: StateMachine::new(),
+            timestep: Self::create_timestep(FixedTimestep::DEFAULT_RATE),
+            simulation_rate: FixedTimestep::DEFAULT_RATE,
+            has_skipped_intro: false,
+            render_resources: RenderResources::new(),
+            states: StateMachine::with_state(States::new(context.clone())),
             input: Input::new(),
+            performance_hud: PerformanceHud::new(),
+            context,
+            settings,
+            window_overrides,
+            settings_path: settings_path.to_path_buf(),
         }
     }
 
     pub fn main_loop(&mut self) {
-        while self.window.is_open() {
+        while self.window.is_open() && !self.states.has_quit() {
             self.handle_events();
             self.update();
             self.draw();
         }
+
+        self.quit();
+    }
+
+    /// Exit hooks, run whether the states quit or the window was closed.
+    fn quit(&mut self) {
+        self.states.quit();
+
+        if let Err(error) = self.input.stop_recording() {
+            Log::error(&format!("couldn't save the recording: {}", error));
+        }
+        if let Err(error) = Profiler::with_profiler(|profiler| profiler.stop_csv()) {
+            Log::error(&format!("couldn't save the timings: {}", error));
+        }
+
+        if self.window.is_open() {
+            self.window.close();
+        }
     }
     
     
     pub fn initialize(&mut self) {
         self.input.initialize();
+        self.apply_settings();
+        Random::set_seed(Random::random_seed());
+    }
+
+    fn apply_settings(&mut self) {
+        self.window.set_vertical_sync_enabled(self.settings.vsync);
+        self.window.set_framerate_limit(self.settings.frame_rate_limit);
+        listener::set_global_volume(self.settings.volume);
+        self.input.set_key_bindings(self.settings.key_bindings.clone());
+    }
+
+    /// Picks up what the options menu changed, the window is opened again only when its
+    /// size or mode changed.
+    fn apply_settings_changes(&mut self) {
+        if *self.context.settings.borrow() == self.settings {
+            return;
+        }
+
+        let settings = self.context.settings.borrow().clone();
+        if settings.resolution != self.settings.resolution || settings.fullscreen != self.settings.fullscreen {
+            self.window_overrides.drop_changed(&self.settings, &settings);
+            self.window = Self::create_window(&settings, &self.window_overrides);
+        }
+        self.settings = settings;
+        self.apply_settings();
+
+        if let Err(error) = self.settings.save(&self.settings_path) {
+            Log::error(&format!("couldn't save the settings: {}", error));
+        }
+    }
+
+    /// Number of simulation steps per second, the drawing still runs as fast as it can.
+    pub fn set_simulation_rate(&mut self, rate: f32) {
+        self.timestep = Self::create_timestep(rate);
+        self.simulation_rate = rate;
+    }
+
+    fn create_timestep(rate: f32) -> FixedTimestep {
+        let max_steps_per_frame = (MAX_SIMULATED_FRAME_TIME * rate).round().max(1.0) as u32;
+        FixedTimestep::new(rate).with_max_steps_per_frame(max_steps_per_frame)
+    }
+
+    /// Starts in the menu instead of the intro.
+    pub fn skip_intro(&mut self) {
+        self.states = StateMachine::with_state(States::after_intro(self.context.clone()));
+        self.has_skipped_intro = true;
+    }
+
+    /// Records the whole run into the file, which is written when the program closes.
+    pub fn record_to(&mut self, path: &Path) {
+        Log::info(&format!("recording to {}", path.display()));
+        let setup = ReplaySetup {
+            simulation_rate: self.simulation_rate,
+            level_path: Some(self.context.level_path.clone()),
+            skip_intro: self.has_skipped_intro,
+        };
+        self.input.start_recording(path, Random::get_seed(), setup);
+    }
+
+    /// Plays the run recorded in the file, started the way it was recorded.
+    pub fn replay_from(&mut self, path: &Path) -> io::Result<()> {
+        let replay = Replay::load(path)?;
+        Log::info(&format!("replaying {} with the seed {}", path.display(), replay.seed));
+        self.apply_replay_setup(&replay.setup)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        Random::set_seed(replay.seed);
+        self.input.start_playback(replay);
+        Ok(())
+    }
+
+    /// What the command line asked for differently is replaced, with a warning, so the replay
+    /// doesn't diverge.
+    fn apply_replay_setup(&mut self, setup: &ReplaySetup) -> Result<(), String> {
+        if setup.simulation_rate != self.simulation_rate {
+            Log::warn(&format!(
+                "the replay is played at the {} steps per second it was recorded at, not {}",
+                setup.simulation_rate, self.simulation_rate
+            ));
+            self.set_simulation_rate(setup.simulation_rate);
+        }
+
+        let level_path = setup.level_path.clone().unwrap_or_else(|| PathBuf::from(DEFAULT_LEVEL_PATH));
+        if level_path != self.context.level_path {
+            Log::warn(&format!("the replay is played on the level it was recorded on, {}", level_path.display()));
+        }
+        check_level(&level_path)?;
+        self.context.level_path = level_path;
+
+        if setup.skip_intro != self.has_skipped_intro {
+            Log::warn("the replay starts from where it was recorded, whatever --skip-intro says");
+        }
+        if setup.skip_intro {
+            self.skip_intro();
+        } else {
+            self.states = StateMachine::with_state(States::new(self.context.clone()));
+            self.has_skipped_intro = false;
+        }
+
+        Ok(())
     }
     
     
     fn update(&mut self){
-        let delta_time = self.timer.restart().as_seconds();
-        self.input.update(delta_time);
-        self.states.update(delta_time);
+        self.apply_settings_changes();
+        let frame_time = self.timer.restart().as_seconds();
+        //the clock is read once per frame, so the previous frame ends here and with the real
+        //time even while a replay feeds the recorded one to the simulation
+        Profiler::end_frame(frame_time);
+        self.input.update_mouse_world_position(&self.window);
+        self.input.set_context(self.states.get_input_context());
+        self.input.update(frame_time);
+
+        //a replay gives back the recorded frame times, so it runs the same steps
+        let steps = self.timestep.advance(self.input.get_delta_time());
+        Profiler::measure(Phase::Update, || {
+            for _ in 0..steps {
+                self.states.update(self.timestep.get_step());
+            }
+        });
         self.states.handle_input(&self.input);
+
+        if self.input.chord_just_pressed(&TIMINGS_CAPTURE_CHORD) {
+            self.toggle_timings_capture();
+        } else if self.input.just_pressed(Keys::TogglePerformanceHud) {
+            self.performance_hud.toggle();
+        }
+    }
+
+    fn toggle_timings_capture(&mut self) {
+        let path = Path::new(TIMINGS_CAPTURE_PATH);
+        let was_writing = Profiler::with_profiler(|profiler| profiler.is_writing_csv());
+        let result = Profiler::with_profiler(|profiler| {
+            if was_writing { profiler.stop_csv() } else { profiler.start_csv(path) }
+        });
+
+        match result {
+            Ok(()) if was_writing => Log::info(&format!("saved the timings to {}", path.display())),
+            Ok(()) => Log::info(&format!("capturing the timings to {}", path.display())),
+            Err(error) => Log::error(&format!("couldn't capture the timings to {}: {}", path.display(), error)),
+        }
     }
     
     fn draw(&mut self){
         self.window.clear(Color::BLACK);
-        self.states.draw(&mut self.window);
+        let mut renderer = SfmlRenderer::new(&mut self.window, &mut self.render_resources);
+        let alpha = self.timestep.get_alpha();
+        Profiler::measure(Phase::Draw, || self.states.draw(&mut renderer, alpha));
+        self.performance_hud.draw(&mut renderer, alpha);
         self.window.display();
     }
     
@@ -56,18 +243,30 @@ impl Program {
         while let Some(event) = self.window.poll_event() {
             match event {
                 Event::Closed => self.window.close(),
-                _ => {}
+                //without a new view the game would be stretched over the resized window
+                Event::Resized { width, height } => {
+                    let view = View::from_rect(&FloatRect::new(0.0, 0.0, width as f32, height as f32));
+                    self.window.set_view(&view);
+                }
+                //a replay only gets what was recorded, anything else would make it diverge
+                _ if self.input.is_replaying() => {}
+                _ => {
+                    self.input.on_event(&event);
+                    self.states.on_event(&event);
+                }
             }
         }
     }
 
-    fn create_window() -> RenderWindow {
+    fn create_window(settings: &Settings, window_overrides: &WindowOverrides) -> RenderWindow {
+        let (width, height) = window_overrides.get_resolution(settings);
         let vide_mode = VideoMode {
-            width: 1280,
-            height: 720,
+            width,
+            height,
             ..VideoMode::desktop_mode()
         };
+        let style = if window_overrides.is_fullscreen(settings) { Style::FULLSCREEN } else { Style::DEFAULT };
 
-        RenderWindow::new(vide_mode, "", Style::DEFAULT, &ContextSettings::default())
+        RenderWindow::new(vide_mode, "", style, &ContextSettings::default())
     }
 }
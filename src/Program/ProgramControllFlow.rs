@@ -1,15 +1,40 @@
-use sfml::graphics::RenderWindow;
+use std::{cell::RefCell, path::PathBuf, rc::Rc};
 
-use crate::{ControllFlow::{Interfaces::State, FlowState}, Objects::Interfaces::{Drawable, Updatable}, InputSystem::{InputConsumer, Input}};
+use sfml::window::Event;
 
-use self::InnerStates::{MainIntroInnerState, MainMenuInnerState, GamePlayInnerState};
+use crate::{ControllFlow::{Interfaces::State, FlowState, Transition, TransitionEffects::{SlideDirection, TransitionEffect}}, Objects::Interfaces::{Drawable, Renderer, Updatable}, InputSystem::{InputConsumer, Input, InputContext}, Settings::Settings};
+
+use self::InnerStates::{MainIntroInnerState, MainMenuInnerState, GamePlayInnerState, PauseInnerState, OptionsInnerState, CreditsInnerState, ResultsInnerState};
 
 mod InnerStates;
+pub use self::InnerStates::{HeadlessSimulation, DEFAULT_LEVEL_PATH, check_level};
+
+/// What the states share with the program, handed on to every state they build.
+#[derive(Clone)]
+pub struct ProgramContext {
+    /// Edited by the options, the program applies and saves what changed.
+    pub settings: Rc<RefCell<Settings>>,
+    /// Level started from the menu.
+    pub level_path: PathBuf,
+}
+
+impl ProgramContext {
+    pub fn new(settings: Settings, level_path: PathBuf) -> Self {
+        Self {
+            settings: Rc::new(RefCell::new(settings)),
+            level_path,
+        }
+    }
+}
 
 pub enum States {
     Intro(MainIntroInnerState),
     Menu(MainMenuInnerState),
     GamePlay(GamePlayInnerState),
+    Pause(PauseInnerState),
+    Options(OptionsInnerState),
+    Credits(CreditsInnerState),
+    Results(ResultsInnerState),
 }
 
 impl State<States> for States {
@@ -33,39 +58,88 @@ impl State<States> for States {
 
             States::GamePlay(game_play) => {
                 game_play.update(delta_time);
-                FlowState::Update
+                game_play.get_flow_state()
             },
+
+            States::Pause(pause) => {
+                pause.update(delta_time);
+                pause.get_flow_state()
+            }
+
+            States::Options(options) => {
+                options.update(delta_time);
+                options.get_flow_state()
+            }
+
+            States::Credits(credits) => {
+                credits.update(delta_time);
+                credits.get_flow_state()
+            }
+
+            States::Results(results) => {
+                results.update(delta_time);
+                results.get_flow_state()
+            }
         }
     }
 
-    fn onExit(&mut self) -> Option<States> {
+    fn onExit(&mut self) -> Transition<States> {
         match self{
-            States::Intro(_) =>{
-                Some(States::Menu(MainMenuInnerState::new()))
+            States::Intro(intro) =>{
+                Transition::Replace(States::after_intro(intro.get_context()))
             },
             States::Menu(menu) => {
-                let chosen_state = menu.get_chosen_state();
+                let transition = menu.get_chosen_transition();
                 menu.reset();
 
-                chosen_state
+                transition
             }
-            States::GamePlay(game_play) => todo!(),
+            States::GamePlay(game_play) => game_play.get_chosen_transition(),
+            States::Pause(_) => Transition::Pop,
+            States::Options(_) => Transition::Pop,
+            States::Credits(_) => Transition::Pop,
+            States::Results(results) => Transition::Replace(States::Menu(MainMenuInnerState::new(results.get_context()))),
         }
     }
+
+    fn get_transition_effect(&self) -> TransitionEffect {
+        match self {
+            States::Intro(_) => TransitionEffect::FadeToBlack { duration: 0.8 },
+            States::Menu(menu) => menu.get_chosen_transition_effect(),
+            States::GamePlay(game_play) => game_play.get_chosen_transition_effect(),
+            States::Pause(_) => TransitionEffect::CrossFade { duration: 0.2 },
+            States::Options(_) => TransitionEffect::Slide { duration: 0.4, direction: SlideDirection::Right },
+            States::Credits(_) => TransitionEffect::Slide { duration: 0.4, direction: SlideDirection::Down },
+            States::Results(_) => TransitionEffect::FadeToBlack { duration: 0.6 },
+        }
+    }
+
+    fn is_overlay(&self) -> bool {
+        matches!(self, States::Pause(_))
+    }
 }
 
-impl Default for States{
-    fn default() -> Self {
-        Self::Intro(MainIntroInnerState::new())
+impl States {
+    pub fn new(context: ProgramContext) -> Self {
+        States::Intro(MainIntroInnerState::new(context))
+    }
+
+    /// First state when the intro is skipped.
+    pub fn after_intro(context: ProgramContext) -> Self {
+        States::Menu(MainMenuInnerState::new(context))
     }
 }
 
 impl Drawable for States{
-    fn draw(&mut self, window: &mut RenderWindow) {
+    fn draw(&mut self, renderer: &mut dyn Renderer, alpha: f32) {
         match self {
-            States::Intro(intro) => intro.draw(window),
-            States::Menu(menu) => menu.draw(window),
-            States::GamePlay(game_play) => game_play.draw(window),
+            States::Intro(intro) => intro.draw(renderer, alpha),
+            States::Menu(menu) => menu.draw(renderer, alpha),
+            States::GamePlay(game_play) => game_play.draw(renderer, alpha),
+            States::Pause(pause) => pause.draw(renderer, alpha),
+            States::Options(options) => options.draw(renderer, alpha),
+            States::Credits(credits) => credits.draw(renderer, alpha),
+            States::Results(results) => results.draw(renderer, alpha),
         }
     }
 }
@@ -73,9 +147,34 @@ impl Drawable for States{
 impl InputConsumer for States{
     fn handle_input(&mut self, input: &Input) {
         match self {
-            States::Intro(intro) => {},
+            States::Intro(_) => {},
             States::Menu(menu) => menu.handle_input(input),
             States::GamePlay(game_play) => game_play.handle_input(input),
+            States::Pause(pause) => pause.handle_input(input),
+            States::Options(options) => options.handle_input(input),
+            States::Credits(credits) => credits.handle_input(input),
+            States::Results(results) => results.handle_input(input),
+        }
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        match self {
+            States::Intro(_) => {},
+            States::Menu(menu) => menu.on_event(event),
+            States::GamePlay(game_play) => game_play.on_event(event),
+            States::Pause(pause) => pause.on_event(event),
+            States::Options(options) => options.on_event(event),
+            States::Credits(credits) => credits.on_event(event),
+            States::Results(results) => results.on_event(event),
+        }
+    }
+
+    fn get_input_context(&self) -> InputContext {
+        match self {
+            States::Intro(_) | States::Menu(_) | States::Credits(_) | States::Results(_) => InputContext::Menu,
+            States::GamePlay(_) => InputContext::Gameplay,
+            States::Pause(pause) => pause.get_input_context(),
+            States::Options(options) => options.get_input_context(),
         }
     }
 }
@@ -6,4 +6,16 @@ mod Menu;
 pub use Menu::MainMenuInnerState;
 
 mod GamePlay;
-pub use GamePlay::GamePlayInnerState;
\ No newline at end of file
+pub use GamePlay::{GameOutcome, GameResults, GamePlayInnerState, HeadlessSimulation, DEFAULT_LEVEL_PATH, check_level};
+
+mod Pause;
+pub use Pause::PauseInnerState;
+
+mod Options;
+pub use Options::OptionsInnerState;
+
+mod Credits;
+pub use Credits::CreditsInnerState;
+
+mod Results;
+pub use Results::ResultsInnerState;
\ No newline at end of file
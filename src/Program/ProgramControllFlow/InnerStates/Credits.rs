@@ -0,0 +1,60 @@
+use sfml::graphics::Color;
+
+use crate::{
+    ControllFlow::FlowState,
+    InputSystem::{Input, InputConsumer, Keys},
+    MathUtilities::Vector,
+    Objects::Interfaces::{Drawable, Renderer, Updatable},
+};
+
+const CREDITS: [&str; 4] = [
+    "Vampire Survivors clone",
+    "Written in Rust with SFML",
+    "Font: Gomarice No Continue",
+    "Press Enter or Esc to go back",
+];
+
+pub struct CreditsInnerState {
+    should_go_back: bool,
+
+    start_position: Vector,
+    spacing: f32,
+}
+
+impl CreditsInnerState {
+    pub fn new() -> Self {
+        Self {
+            should_go_back: false,
+            start_position: Vector::new(100.0, 100.0),
+            spacing: 50.0,
+        }
+    }
+
+    pub fn get_flow_state(&self) -> FlowState {
+        if self.should_go_back {
+            return FlowState::Exit;
+        }
+        FlowState::Update
+    }
+}
+
+impl Updatable for CreditsInnerState {
+    fn update(&mut self, _delta_time: f32) {}
+}
+
+impl Drawable for CreditsInnerState {
+    fn draw(&mut self, renderer: &mut dyn Renderer, _alpha: f32) {
+        for (i, line) in CREDITS.iter().enumerate() {
+            let position = self.start_position + Vector::new(0.0, i as f32 * self.spacing);
+            renderer.draw_text(line, 20, position, Color::WHITE);
+        }
+    }
+}
+
+impl InputConsumer for CreditsInnerState {
+    fn handle_input(&mut self, input: &Input) {
+        if input.just_pressed(Keys::Enter) || input.just_pressed(Keys::Esc) {
+            self.should_go_back = true;
+        }
+    }
+}
@@ -1,53 +1,139 @@
-use std::{fs::File, path::Path};
-
-use sfml::graphics::RenderWindow;
+use std::{
+    cell::RefCell,
+    path::Path,
+    rc::Rc,
+};
 
 use crate::{
-    InputSystem::InputConsumer,
-    Objects::Interfaces::{Drawable, Updatable},
+    ControllFlow::{FlowState, StateMachine, Transition, TransitionEffects::TransitionEffect},
+    InputSystem::{InputConsumer, Keys},
+    Objects::{
+        Interfaces::{Drawable, Renderer, Updatable},
+        Log,
+    },
+    Program::ProgramControllFlow::{ProgramContext, States},
 };
 
 use self::GameObjects::Level::{Level, LevelConfiguration};
+use self::GamePlayStates::{GamePlayContext, GamePlayStates as GamePlaySubStates};
+
+use super::{PauseInnerState, ResultsInnerState};
+
+pub use self::GamePlayStates::GameOutcome;
 
 mod GameObjects;
+mod GamePlayStates;
+
+mod Headless;
+pub use Headless::HeadlessSimulation;
+
+pub const DEFAULT_LEVEL_PATH: &str = "resources/GameplayConfig/Levels/Level_1.json";
+
+/// Loads the level once, so a broken level is reported before the game starts rather than
+/// when it is played.
+pub fn check_level(path: &Path) -> Result<(), String> {
+    LevelConfiguration::load(path).map(|_| ())
+}
+
+/// How the level went, shown on the results screen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GameResults {
+    pub outcome: GameOutcome,
+    pub wave_number: usize,
+    pub wave_count: usize,
+    pub defeated_enemy_count: usize,
+}
 
 pub struct GamePlayInnerState {
-    currently_loaded_level: Level,
+    currently_loaded_level: Rc<RefCell<Level>>,
+    states: StateMachine<GamePlaySubStates>,
+
+    should_pause: bool,
+    program_context: ProgramContext,
 }
 
 impl GamePlayInnerState {
-    pub fn new() -> Self {
-        let file_path = Path::new("resources/GameplayConfig/Levels/Level_1.json");
-        let file =File::open(file_path).unwrap();
-        let level_config: LevelConfiguration = serde_json::from_reader(file).expect("error parsing file");
+    pub fn new(program_context: ProgramContext) -> Self {
+        let level_path = &program_context.level_path;
+        Log::info(&format!("loading the level {}", level_path.display()));
+        let level_config = LevelConfiguration::from_file(level_path);
+
+        let context = GamePlayContext {
+            level: Rc::new(RefCell::new(Level::new(level_config))),
+        };
         Self {
-            currently_loaded_level: Level::new(level_config),
+            currently_loaded_level: context.level.clone(),
+            states: StateMachine::with_state(GamePlaySubStates::new(context)),
+            should_pause: false,
+            program_context,
         }
     }
 
-    fn update_level(&mut self, delta_time: f32) {
-        self.currently_loaded_level.update(delta_time);
+    pub fn get_flow_state(&self) -> FlowState {
+        if self.should_pause || self.get_outcome().is_some() {
+            return FlowState::Exit;
+        }
+        FlowState::Update
     }
 
-    fn draw_level(&mut self, window: &mut RenderWindow) {
-        self.currently_loaded_level.draw(window);
+    /// Set once the level is over and the player confirmed its end screen.
+    pub fn get_outcome(&self) -> Option<GameOutcome> {
+        self.states.get_current_state().get_outcome()
+    }
+
+    pub fn get_results(&self) -> Option<GameResults> {
+        let level = self.currently_loaded_level.borrow();
+        self.get_outcome().map(|outcome| GameResults {
+            outcome,
+            wave_number: level.get_current_wave_number(),
+            wave_count: level.get_wave_count(),
+            defeated_enemy_count: level.get_defeated_enemy_count(),
+        })
+    }
+
+    pub fn get_chosen_transition(&mut self) -> Transition<States> {
+        match self.get_results() {
+            Some(results) => Transition::Replace(States::Results(ResultsInnerState::new(results, self.program_context.clone()))),
+            None => {
+                //cleared so the gameplay continues when the pause is popped
+                self.should_pause = false;
+                Transition::Push(States::Pause(PauseInnerState::new()))
+            }
+        }
+    }
+
+    pub fn get_chosen_transition_effect(&self) -> TransitionEffect {
+        match self.get_outcome() {
+            Some(_) => TransitionEffect::FadeToBlack { duration: 0.8 },
+            None => TransitionEffect::CrossFade { duration: 0.2 },
+        }
     }
 }
 
 impl Updatable for GamePlayInnerState {
     fn update(&mut self, delta_time: f32) {
-        self.update_level(delta_time);
+        if self.should_pause {
+            return;
+        }
+
+        self.states.update(delta_time);
     }
 }
 
 impl Drawable for GamePlayInnerState {
-    fn draw(&mut self, window: &mut sfml::graphics::RenderWindow) {
-        self.draw_level(window);
+    fn draw(&mut self, renderer: &mut dyn Renderer, alpha: f32) {
+        self.states.draw(renderer, alpha);
     }
 }
 
 impl InputConsumer for GamePlayInnerState {
     fn handle_input(&mut self, input: &crate::InputSystem::Input) {
-        self.currently_loaded_level.handle_input(input);
+        //focus is read from the input rather than the events so replays pause the same way
+        if !input.has_focus() || input.just_pressed(Keys::Esc) {
+            self.should_pause = true;
+            return;
+        }
+
+        self.states.handle_input(input);
     }
 }
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod Tests;
+
 mod Player;
 mod Weapons;
 mod Enemy;
@@ -1,80 +1,118 @@
-use sfml::graphics::{RectangleShape, RenderTarget, Transformable};
-use sfml::system::Vector2f;
+use sfml::graphics::Color;
 
 use crate::CollisionSystem::{
-    Collidable, Collider, CollisionInfo, CollisionMask, CollisionShape, Rectangle,
+    Collidable, Collider, CollisionInfo, CollisionMask, CollisionResponse, CollisionShape,
+    ContactPhase, EntityId, Rectangle,
 };
 use crate::MathUtilities::{Position, Vector};
-use crate::Objects::Interfaces::{Drawable, Initializable, Updatable, Destroyable};
+use crate::Objects::Interfaces::{Drawable, Updatable, Destroyable, Renderer};
 
 pub mod EnemySpawners;
 
 pub struct Enemy {
+    id: EntityId,
     position: Position,
-    rectangle: Rectangle,
+    previous_position: Position,
+    shape: CollisionShape,
+    //in degrees, towards where the enemy walks
+    rotation: f32,
     speed: f32,
     health: f32,
+    mass: f32,
 }
 
 impl Enemy {
     pub fn new(position: Position) -> Self {
         Self {
-            position: position,
-            rectangle: Rectangle::new(40.0, 40.0),
+            id: EntityId::new(),
+            position,
+            previous_position: position,
+            shape: CollisionShape::Rectangle(Rectangle::new(40.0, 40.0)),
+            rotation: 0.0,
             speed: 20.0,
             health: 100.0,
+            mass: 1.0,
         }
     }
 
-    pub fn walk_towards(&mut self, target_position: Position, delta_time: f32) {
-        let option_direction = (target_position - self.position).normal();
-        let direction = match option_direction {
-            Some(direction) => self.position += direction * self.speed * delta_time,
-            None => {}
-        };
+    pub fn with_shape(mut self, shape: CollisionShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    pub fn with_mass(mut self, mass: f32) -> Self {
+        self.mass = mass;
+        self
     }
 
-    fn prevent_walking_on_other_objects(&mut self, info: CollisionInfo) {
-        self.position = self.position - info.collision_depth;
+    pub fn walk_towards(&mut self, target_position: Position, delta_time: f32) {
+        if let Some(direction) = (target_position - self.position).normal() {
+            self.position += direction * self.speed * delta_time;
+            self.rotation = f32::atan2(direction.get_y(), direction.get_x()).to_degrees();
+        }
     }
 }
 
 impl Updatable for Enemy {
-    fn update(&mut self, delta_time: f32) {}
+    fn update(&mut self, _delta_time: f32) {
+        self.previous_position = self.position;
+    }
 }
 
 impl Drawable for Enemy {
-    fn draw(&mut self, window: &mut sfml::graphics::RenderWindow) {
-        let mut rect = RectangleShape::new();
-        let size = Vector::new(self.rectangle.width, self.rectangle.height);
-        rect.set_size(size);
-        rect.set_position(self.position);
-        rect.set_origin(size * 0.5);
-        window.draw(&rect);
+    fn draw(&mut self, renderer: &mut dyn Renderer, alpha: f32) {
+        let position = Vector::lerp(self.previous_position, self.position, alpha);
+        match self.shape {
+            CollisionShape::Rectangle(rectangle) => {
+                let size = Vector::new(rectangle.width, rectangle.height);
+                renderer.draw_rectangle(position - size * 0.5, size, Color::WHITE);
+            }
+            //the other shapes are drawn as they collide, turned with the enemy
+            shape => {
+                let outline = Collider::new(shape, position)
+                    .with_rotation(self.rotation)
+                    .get_outline();
+                renderer.draw_polygon(&outline, Color::WHITE);
+            }
+        }
     }
 }
 
 impl Collidable for Enemy {
+    fn get_id(&self) -> EntityId {
+        self.id
+    }
+
     fn get_collider(&self) -> Collider {
-        Collider {
-            shape: CollisionShape::Rectangle(self.rectangle),
-            position: self.position,
-        }
+        Collider::new(self.shape, self.position)
+            .with_rotation(self.rotation)
+            .with_mass(self.mass)
     }
 
     fn get_mask(&self) -> CollisionMask {
         CollisionMask::Enemy
     }
 
-    fn react_to_collision(&mut self, info: CollisionInfo, other_mask: CollisionMask) {
-        match other_mask {
-            CollisionMask::Player => {
-                self.prevent_walking_on_other_objects(info);
-            }
-            CollisionMask::Enemy => {
-                self.prevent_walking_on_other_objects(info);
+    fn translate(&mut self, offset: Vector) {
+        self.position += offset;
+    }
+
+    fn react_to_collision(
+        &mut self,
+        _info: CollisionInfo,
+        other_mask: CollisionMask,
+        response: CollisionResponse,
+        phase: ContactPhase,
+    ) {
+        //solid contacts are already resolved by the symulation
+        match (response, phase) {
+            //once per contact, not on every frame of the overlap
+            (CollisionResponse::Trigger, ContactPhase::Enter)
+                if other_mask == CollisionMask::Weapon =>
+            {
+                self.health -= 10.0;
             }
-            CollisionMask::Weapon => self.health -= 10.0,
+            _ => { /* do nothing */ }
         }
     }
 }
@@ -1,8 +1,9 @@
-use rand::{thread_rng, Rng};
+use rand::{rngs::StdRng, Rng};
 use crate::{
     MathUtilities::Position,
     Objects::{
         Interfaces::{Updatable},
+        Random,
         Timers::{BasicTimer, Timer},
     },
     Program::ProgramControllFlow::InnerStates::GamePlay::GameObjects::{Level::Wave, Spawner::Spawner},
@@ -13,12 +14,14 @@ use super::Enemy;
 pub struct WaveSpawner {
     timer: BasicTimer,
     wave_data: Wave,
+    rng: StdRng,
 }
 impl WaveSpawner {
     pub fn new(wave: Wave) -> Self {
         Self {
             timer: BasicTimer::new(wave.enemy_spawn_delay_in_seconds),
             wave_data: wave,
+            rng: Random::create_rng(),
         }
     }
 }
@@ -51,10 +54,9 @@ impl WaveSpawner {
         self.wave_data.enemy_count -= 1;
     }
 
-    fn create_enemy_at_random_position_outside_window(&self)->Enemy{
-        let mut rng = thread_rng(); 
-        let mut side_rng = thread_rng();
-        let side = side_rng.gen_range(0..4);
+    fn create_enemy_at_random_position_outside_window(&mut self)->Enemy{
+        let rng = &mut self.rng;
+        let side = rng.gen_range(0..4);
         
         //sooooo lazyyyyyy....
         let mut random_pos = Position::default();
@@ -70,6 +72,8 @@ impl WaveSpawner {
 
 
         Enemy::new(random_pos)
+            .with_shape(self.wave_data.enemy_shape.to_collision_shape())
+            .with_mass(self.wave_data.enemy_mass)
     }
 }
 
@@ -1,20 +1,22 @@
-use std::{ops::Index, vec};
-
-use sfml::graphics::RenderWindow;
+use std::{fs::File, path::Path, vec};
 
 use crate::{
-    CollisionSystem::{Collidable, Symulation::WordSymulation},
-    InputSystem::{Input, InputConsumer},
-    MathUtilities::Position,
+    CollisionSystem::{
+        Capsule, Circle, Collidable, CollisionMask, CollisionShape, ConvexPolygon, EntityId,
+        OrientedRectangle, Rectangle, Symulation::WordSymulation,
+    },
+    InputSystem::{Input, InputConsumer, Keys},
+    MathUtilities::Point,
     Objects::{
-        Interfaces::{Destroyable, Drawable, Initializable, Updatable},
-        Timers::{BasicTimer, Timer},
+        Interfaces::{Destroyable, Drawable, Initializable, Renderer, Updatable},
+        Log,
+        Profiler::{self, Counter, Phase},
     },
 };
 
 use super::{
     Spawner::Spawner,
-    Weapons::{self, SimpleMissile::SimpleMissile},
+    Weapons::SimpleMissile::SimpleMissile,
 };
 
 use super::{
@@ -23,19 +25,153 @@ use super::{
 };
 
 use serde::{Deserialize, Serialize};
+use sfml::graphics::Color;
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct LevelConfiguration {
     pub name: String,
     pub waves: Vec<Wave>,
+    #[serde(default)]
+    pub player_pushable_by_enemies: bool,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+impl LevelConfiguration {
+    /// Panics when the level can't be loaded, `load` tells why instead.
+    pub fn from_file(file_path: &Path) -> Self {
+        Self::load(file_path).unwrap()
+    }
+
+    pub fn load(file_path: &Path) -> Result<Self, String> {
+        let file = File::open(file_path)
+            .map_err(|error| format!("couldn't open {}: {}", file_path.display(), error))?;
+        let configuration: Self = serde_json::from_reader(file)
+            .map_err(|error| format!("couldn't parse {}: {}", file_path.display(), error))?;
+
+        //the level starts with the first wave, it can't do without one
+        if configuration.waves.is_empty() {
+            return Err(format!("{} has no waves", file_path.display()));
+        }
+        //the solver divides by the mass, infinite is fine and makes the enemies immovable
+        let has_massless_wave = configuration
+            .waves
+            .iter()
+            .any(|wave| wave.enemy_mass.is_nan() || wave.enemy_mass <= 0.0);
+        if has_massless_wave {
+            return Err(format!(
+                "{} has a wave whose enemy mass isn't positive",
+                file_path.display()
+            ));
+        }
+        for (index, wave) in configuration.waves.iter().enumerate() {
+            wave.enemy_shape.validate().map_err(|error| {
+                format!(
+                    "{} has a broken enemy shape in wave {}, {}",
+                    file_path.display(),
+                    index + 1,
+                    error
+                )
+            })?;
+        }
+        Ok(configuration)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct Wave {
     pub enemy_count: usize,
     pub enemy_spawn_delay_in_seconds: f32,
+    //heavier enemies push the lighter ones out of their way
+    #[serde(default = "Wave::default_enemy_mass")]
+    pub enemy_mass: f32,
+    #[serde(default)]
+    pub enemy_shape: EnemyShape,
+}
+
+/// Collision shape of the enemies of a wave, the sizes are in pixels.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnemyShape {
+    Rectangle { width: f32, height: f32 },
+    OrientedRectangle { width: f32, height: f32 },
+    Circle { radius: f32 },
+    Capsule { length: f32, radius: f32 },
+    /// Vertices around the position of the enemy, facing right.
+    ConvexPolygon { vertices: Vec<(f32, f32)> },
+}
+
+impl EnemyShape {
+    pub fn to_collision_shape(&self) -> CollisionShape {
+        match self {
+            EnemyShape::Rectangle { width, height } => {
+                CollisionShape::Rectangle(Rectangle::new(*width, *height))
+            }
+            EnemyShape::OrientedRectangle { width, height } => {
+                CollisionShape::OrientedRectangle(OrientedRectangle::new(*width, *height))
+            }
+            EnemyShape::Circle { radius } => CollisionShape::Circle(Circle::new(*radius)),
+            EnemyShape::Capsule { length, radius } => {
+                CollisionShape::Capsule(Capsule::new(*length, *radius))
+            }
+            EnemyShape::ConvexPolygon { vertices } => {
+                CollisionShape::ConvexPolygon(ConvexPolygon::new(&Self::to_points(vertices)))
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            EnemyShape::ConvexPolygon { vertices } => {
+                ConvexPolygon::try_new(&Self::to_points(vertices)).map(|_| ())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn to_points(vertices: &[(f32, f32)]) -> Vec<Point> {
+        vertices.iter().map(|(x, y)| Point::new(*x, *y)).collect()
+    }
+}
+
+impl Default for EnemyShape {
+    fn default() -> Self {
+        EnemyShape::Rectangle {
+            width: 40.0,
+            height: 40.0,
+        }
+    }
+}
+
+impl Wave {
+    fn default_enemy_mass() -> f32 {
+        1.0
+    }
+}
+
+impl Default for Wave {
+    fn default() -> Self {
+        Self {
+            enemy_count: 0,
+            enemy_spawn_delay_in_seconds: 0.0,
+            enemy_mass: Self::default_enemy_mass(),
+            enemy_shape: EnemyShape::default(),
+        }
+    }
+}
+
+//an enemy this close to the cursor is aimed at instead of the cursor
+const CURSOR_AIM_RADIUS: f32 = 30.0;
+const AIM_HIT_COLOR: Color = Color::RED;
+const AIM_MISS_COLOR: Color = Color::rgba(255, 255, 255, 120);
+const AIM_NORMAL_LENGTH: f32 = 20.0;
+const MISSILE_REACH_COLOR: Color = Color::rgba(255, 165, 0, 160);
+
+/// What the missiles are sent towards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AimMode {
+    NearestEnemy,
+    Cursor,
 }
 
 pub struct Level {
@@ -49,22 +185,43 @@ pub struct Level {
     wave_spawner: WaveSpawner,
 
     configuration: LevelConfiguration,
-    current_wave: usize,
+    started_wave_count: usize,
+    defeated_enemy_count: usize,
+
+    is_collision_debug_visible: bool,
+
+    aim_mode: AimMode,
+    cursor_position: Point,
 }
 
 impl Level {
     pub fn new(level_configuration: LevelConfiguration) -> Self {
+        let mut new_level = Self::new_headless(level_configuration);
+        new_level.initialize();
+        new_level
+    }
+
+    /// Level which never loads its textures, so it runs without a window or a graphics context
+    /// as long as it isn't drawn.
+    pub fn new_headless(level_configuration: LevelConfiguration) -> Self {
         let mut new_level = Self {
             player: Player::new(),
             enemies: vec![],
             weapons: vec![],
             collision_symulation: WordSymulation::new(),
-            wave_spawner: WaveSpawner::new(level_configuration.waves.get(0).unwrap().clone()),
+            wave_spawner: WaveSpawner::new(level_configuration.waves.first().unwrap().clone()),
             weapon_spawner: SimpleMissileSpawner::new(0.2),
             configuration: level_configuration,
-            current_wave: 0,
+            started_wave_count: 0,
+            defeated_enemy_count: 0,
+            is_collision_debug_visible: false,
+            aim_mode: AimMode::NearestEnemy,
+            cursor_position: Point::default(),
         };
-        new_level.initialize();
+        new_level
+            .player
+            .set_pushable_by_enemies(new_level.configuration.player_pushable_by_enemies);
+        new_level.weapon_spawner.start_spawning();
         new_level
     }
 
@@ -78,7 +235,9 @@ impl Level {
             enemy.walk_towards(self.player.get_position(), delta_time);
         }
 
+        let enemy_count = self.enemies.len();
         Level::delete_objects_ready_to_destroy(&mut self.enemies);
+        self.defeated_enemy_count += enemy_count - self.enemies.len();
     }
 
     fn update_weapons(&mut self, delta_time: f32) {
@@ -90,14 +249,14 @@ impl Level {
     }
 
     fn delete_objects_ready_to_destroy<T>(destroyables: &mut Vec<T>) where T: Destroyable{
-        let to_delete = Level::get_indices_of_objects_to_delete(&destroyables);
+        let to_delete = Level::get_indices_of_objects_to_delete(destroyables);
         for i in 0..to_delete.len() {
             //after removing element rest of them are being shifted by one to the left so next element to remove is its index minus removed elements count
             destroyables.remove(to_delete.get(i).unwrap() - i);
         }
     }
 
-    fn get_indices_of_objects_to_delete<T>(destroyables: &Vec<T>) -> Vec<usize> where T: Destroyable{
+    fn get_indices_of_objects_to_delete<T>(destroyables: &[T]) -> Vec<usize> where T: Destroyable{
         let mut to_delete: Vec<usize> = vec![];
         for i in 0..destroyables.len() {
             if destroyables.get(i).unwrap().should_be_destroyed() {
@@ -111,18 +270,10 @@ impl Level {
     fn update_wave_spawner(&mut self, delta_time: f32) {
         self.wave_spawner.update(delta_time);
 
+        //the next wave is started by whoever runs the level, there may be a break before it
         if self.wave_spawner.finished_spawning() {
-            self.current_wave += 1usize;
-
-            let new_wave = self.configuration.waves.get(self.current_wave);
-            match new_wave {
-                Some(wave) => {
-                    self.wave_spawner = WaveSpawner::new(wave.clone());
-                    self.wave_spawner.start_spawning();
-                }
-
-                None => self.wave_spawner.stop_spawning(),
-            }
+            self.wave_spawner.stop_spawning();
+            return;
         }
 
         if self.wave_spawner.should_spawn() {
@@ -134,28 +285,107 @@ impl Level {
     fn update_weapon_spawners(&mut self, delta_time: f32) {
         self.weapon_spawner.update(delta_time);
 
-        if self.enemies.is_empty() || !self.weapon_spawner.should_spawn() {
+        if !self.weapon_spawner.should_spawn() {
             return;
         }
 
-        let mut min_distance_enemy_position = self.enemies.get(0).unwrap().get_collider().position;
-        for enemy in &self.enemies {
-            let distance_from_enemy =
-                (enemy.get_collider().position - self.player.get_position()).length();
-            let min_distance = (min_distance_enemy_position - self.player.get_position()).length();
-            if distance_from_enemy < min_distance {
-                min_distance_enemy_position = enemy.get_collider().position;
-            }
-        }
+        let target_position = match self.get_target_position() {
+            Some(target_position) => target_position,
+            None => return,
+        };
+
         self.weapon_spawner
             .set_spawn_position(self.player.get_position());
         self.weapon_spawner
-            .set_target_position(min_distance_enemy_position);
+            .set_target_position(target_position);
         self.weapons.extend(self.weapon_spawner.spawn());
         self.weapon_spawner.start_spawning();
     }
 
-    fn update_word(&mut self, delta_time: f32) {
+    /// Starts spawning the first wave which wasn't started yet, if there is one.
+    pub fn start_next_wave(&mut self) {
+        let wave = match self.configuration.waves.get(self.started_wave_count) {
+            Some(wave) => wave.clone(),
+            None => return,
+        };
+
+        self.wave_spawner = WaveSpawner::new(wave);
+        self.wave_spawner.start_spawning();
+        self.started_wave_count += 1;
+        Log::debug(&format!("starting the wave {}/{}", self.started_wave_count, self.get_wave_count()));
+    }
+
+    pub fn has_next_wave(&self) -> bool {
+        self.started_wave_count < self.configuration.waves.len()
+    }
+
+    /// Counted from 1, 0 before the first wave.
+    pub fn get_current_wave_number(&self) -> usize {
+        self.started_wave_count
+    }
+
+    pub fn get_wave_count(&self) -> usize {
+        self.configuration.waves.len()
+    }
+
+    /// Everything the wave had to spawn was spawned and defeated.
+    pub fn is_wave_cleared(&self) -> bool {
+        self.started_wave_count > 0 && self.wave_spawner.finished_spawning() && self.enemies.is_empty()
+    }
+
+    pub fn get_defeated_enemy_count(&self) -> usize {
+        self.defeated_enemy_count
+    }
+
+    /// Health of the player divided by its maximum.
+    pub fn get_player_health_fraction(&self) -> f32 {
+        self.player.get_health() / self.player.get_max_health()
+    }
+
+    pub fn is_player_dead(&self) -> bool {
+        self.player.is_dead()
+    }
+
+    /// Enemies and missiles, with the player.
+    pub fn get_entity_count(&self) -> usize {
+        self.enemies.len() + self.weapons.len() + 1
+    }
+
+    //the enemies are the first collidables of the last detection, so the index of a hit is the
+    //index of the enemy. That detection still holds the enemies it killed, they are removed only
+    //during the next update
+    fn is_live_enemy(&self, index: usize, id: EntityId) -> bool {
+        self.enemies
+            .get(index)
+            .is_some_and(|enemy| enemy.get_id() == id && !enemy.should_be_destroyed())
+    }
+
+    fn get_target_position(&self) -> Option<Point> {
+        match self.aim_mode {
+            //an enemy under the cursor is aimed at, not the edge of it the cursor is on
+            AimMode::Cursor => {
+                let hovered_enemy = self
+                    .collision_symulation
+                    .query_circle(self.cursor_position, CURSOR_AIM_RADIUS, CollisionMask::Enemy)
+                    .into_iter()
+                    .filter(|hit| self.is_live_enemy(hit.index, hit.id))
+                    .max_by(|first, second| first.info.depth.total_cmp(&second.info.depth));
+
+                match hovered_enemy {
+                    Some(hit) => Some(hit.collider.position),
+                    None => Some(self.cursor_position),
+                }
+            }
+            AimMode::NearestEnemy => self
+                .collision_symulation
+                .nearest_matching(self.player.get_position(), CollisionMask::Enemy, |hit| {
+                    self.is_live_enemy(hit.index, hit.id)
+                })
+                .map(|hit| hit.collider.position),
+        }
+    }
+
+    fn update_word(&mut self) {
         let mut collidables: Vec<&mut dyn Collidable> = vec![];
         for enemy in &mut self.enemies {
             collidables.push(enemy);
@@ -167,51 +397,140 @@ impl Level {
 
         collidables.push(&mut self.player);
 
+        //collisions are kept until the next update so the debug overlay can draw them
+        self.collision_symulation.clear_collisions();
         self.collision_symulation.collision_detection(&collidables);
+        self.collision_symulation
+            .resolve_solid_collisions(&mut collidables);
         self.collision_symulation
             .react_to_collisionss(&mut collidables);
-        self.collision_symulation.clear_collisions();
         collidables.clear();
     }
 
-    fn draw_player(&mut self, window: &mut RenderWindow) {
-        self.player.draw(window);
+    fn count_entities(&self) {
+        Profiler::set_counter(Counter::Entities, self.get_entity_count());
+        Profiler::set_counter(Counter::Enemies, self.enemies.len());
+        Profiler::set_counter(Counter::Missiles, self.weapons.len());
+        Profiler::set_counter(Counter::CheckedPairs, self.collision_symulation.get_checked_pair_count());
+        Profiler::set_counter(Counter::CollidingPairs, self.collision_symulation.get_colliding_pair_count());
+    }
+
+    fn draw_player(&mut self, renderer: &mut dyn Renderer, alpha: f32) {
+        self.player.draw(renderer, alpha);
     }
 
-    fn draw_enemies(&mut self, window: &mut RenderWindow) {
+    fn draw_enemies(&mut self, renderer: &mut dyn Renderer, alpha: f32) {
         for enemy in &mut self.enemies {
-            enemy.draw(window);
+            enemy.draw(renderer, alpha);
         }
     }
 
-    fn draw_weapons(&mut self, window: &mut RenderWindow) {
+    fn draw_weapons(&mut self, renderer: &mut dyn Renderer, alpha: f32) {
         for weapon in &mut self.weapons {
-            weapon.draw(window);
+            weapon.draw(renderer, alpha);
+        }
+    }
+
+    fn draw_collision_debug(&mut self, renderer: &mut dyn Renderer) {
+        if self.is_collision_debug_visible {
+            self.collision_symulation.draw_debug(renderer);
+            self.draw_missile_reach_debug(renderer);
+            self.draw_aim_debug(renderer);
+        }
+    }
+
+    /// Enemies close enough along both axes for a missile to reach them.
+    fn draw_missile_reach_debug(&self, renderer: &mut dyn Renderer) {
+        let reach = 2.0 * SimpleMissile::FLY_DISTANCE;
+        let hits = self.collision_symulation.query_rect(
+            self.player.get_position(),
+            Rectangle::new(reach, reach),
+            CollisionMask::Enemy,
+        );
+
+        for hit in hits.iter().filter(|hit| self.is_live_enemy(hit.index, hit.id)) {
+            Self::draw_outline(renderer, &hit.collider.get_outline(), MISSILE_REACH_COLOR);
+        }
+    }
+
+    /// Line along which the next missile would fly, up to the first enemy in its way.
+    fn draw_aim_debug(&self, renderer: &mut dyn Renderer) {
+        let origin = self.player.get_position();
+        let direction = match self
+            .get_target_position()
+            .and_then(|target_position| (target_position - origin).normal())
+        {
+            Some(direction) => direction,
+            None => return,
+        };
+
+        let hit = self.collision_symulation.raycast(
+            origin,
+            direction,
+            SimpleMissile::FLY_DISTANCE,
+            CollisionMask::Enemy,
+        );
+        match hit {
+            Some(hit) if self.is_live_enemy(hit.index, hit.id) => {
+                renderer.draw_line_strip(&[origin, hit.point], AIM_HIT_COLOR);
+                renderer.draw_line_strip(
+                    &[hit.point, hit.point + hit.normal * AIM_NORMAL_LENGTH],
+                    AIM_HIT_COLOR,
+                );
+                Self::draw_outline(renderer, &hit.collider.get_outline(), AIM_HIT_COLOR);
+            }
+            _ => {
+                let end = origin + direction * SimpleMissile::FLY_DISTANCE;
+                renderer.draw_line_strip(&[origin, end], AIM_MISS_COLOR);
+            }
+        }
+    }
+
+    fn draw_outline(renderer: &mut dyn Renderer, outline: &[Point], color: Color) {
+        if let Some(first) = outline.first() {
+            let mut closed_outline = outline.to_vec();
+            closed_outline.push(*first);
+            renderer.draw_line_strip(&closed_outline, color);
         }
     }
 }
 
 impl Updatable for Level {
     fn update(&mut self, delta_time: f32) {
-        self.update_player(delta_time);
-        self.update_weapon_spawners(delta_time);
-        self.update_weapons(delta_time);
+        Profiler::measure(Phase::UpdatePlayer, || self.update_player(delta_time));
+        Profiler::measure(Phase::UpdateWeapons, || self.update_weapons(delta_time));
         self.update_wave_spawner(delta_time);
-        self.update_enemies(delta_time);
-        self.update_word(delta_time);
+        Profiler::measure(Phase::UpdateEnemies, || self.update_enemies(delta_time));
+        Profiler::measure(Phase::UpdateWord, || self.update_word());
+        //aimed with the detection that just ran, so every enemy alive is in it
+        self.update_weapon_spawners(delta_time);
+        self.count_entities();
     }
 }
 
 impl Drawable for Level {
-    fn draw(&mut self, window: &mut sfml::graphics::RenderWindow) {
-        self.draw_player(window);
-        self.draw_enemies(window);
-        self.draw_weapons(window);
+    fn draw(&mut self, renderer: &mut dyn Renderer, alpha: f32) {
+        Profiler::measure(Phase::DrawPlayer, || self.draw_player(renderer, alpha));
+        Profiler::measure(Phase::DrawEnemies, || self.draw_enemies(renderer, alpha));
+        Profiler::measure(Phase::DrawWeapons, || self.draw_weapons(renderer, alpha));
+        self.draw_collision_debug(renderer);
     }
 }
 
 impl InputConsumer for Level {
     fn handle_input(&mut self, input: &Input) {
+        if input.just_pressed(Keys::ToggleDebug) {
+            self.is_collision_debug_visible = !self.is_collision_debug_visible;
+        }
+
+        if input.just_pressed(Keys::ToggleAim) {
+            self.aim_mode = match self.aim_mode {
+                AimMode::NearestEnemy => AimMode::Cursor,
+                AimMode::Cursor => AimMode::NearestEnemy,
+            };
+        }
+        self.cursor_position = input.get_mouse_world_position();
+
         self.player.handle_input(input);
     }
 }
@@ -219,7 +538,5 @@ impl InputConsumer for Level {
 impl Initializable for Level {
     fn initialize(&mut self) {
         self.player.initialize();
-        self.wave_spawner.start_spawning();
-        self.weapon_spawner.start_spawning();
     }
 }
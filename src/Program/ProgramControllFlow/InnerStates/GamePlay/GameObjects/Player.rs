@@ -1,110 +1,139 @@
-use std::sync::mpsc::Receiver;
+use crate::{CollisionSystem::{Collidable, CollisionShape, Circle, CollisionMask, CollisionInfo, CollisionResponse, ContactPhase, EntityId}, MathUtilities::{Vector, Point, Position}, InputSystem::{InputConsumer, Input, Keys}};
+use crate::Objects::Animations::{AnimationPlayer, PlayersAnimations};
 
-use crate::{CollisionSystem::{Collidable, CollisionShape, Circle, CollisionMask, CollisionInfo}, MathUtilities::{Vector, Point, Position, self}, InputSystem::{InputConsumer, Input, Keys}};
-use crate::Objects::Animations::AnimationPlayer;
+use sfml::graphics::Color;
 
-use sfml::{
-    graphics::{RenderTarget, RenderWindow, Transformable, Shape, CircleShape, Color, Sprite, RectangleShape}, system::Vector2f
-};
-
-use crate::Objects::Interfaces::{Drawable, Initializable, Updatable};
+use crate::Objects::Interfaces::{Drawable, Initializable, Renderer, Updatable};
+use crate::Objects::Timers::{BasicTimer, Timer};
 use crate::CollisionSystem::Collider;
 
 pub mod WeaponSpawners;
 
+const DASH_DURATION: f32 = 0.2;
+const DASH_SPEED_MULTIPLIER: f32 = 3.0;
+const DASH_KEYS: [Keys; 4] = [Keys::Left, Keys::Right, Keys::Up, Keys::Down];
+
 pub struct Player {
+    id: EntityId,
     animation_player: AnimationPlayer,
     
     position: Point,
+    //where the player was before the last simulation step, for drawing between steps
+    previous_position: Point,
     move_direction: Vector,
     speed: f32,
     collision_shape: CollisionShape,
     color: Color,
     face_direction: i8,
+    pushable_by_enemies: bool,
+
+    health: f32,
+    max_health: f32,
+    invulnerability_timer: BasicTimer,
+    dash_timer: BasicTimer,
 }
 
 impl Player {
     pub fn new() -> Self {
         Self {
+            id: EntityId::new(),
             position: Point::new(0.0, 0.0),
+            previous_position: Point::new(0.0, 0.0),
             animation_player: AnimationPlayer::new(),
             move_direction: Vector::new(0.0, 0.0),
             speed: 200.0,
             collision_shape: CollisionShape::Circle(Circle::new(40.0)),
             color: Color::RED,
-            face_direction: 1
+            face_direction: 1,
+            pushable_by_enemies: false,
+            health: 100.0,
+            max_health: 100.0,
+            invulnerability_timer: BasicTimer::new(1.0),
+            dash_timer: BasicTimer::new(DASH_DURATION),
         }
     }
 
-    fn create_visual_representation(&self) -> RectangleShape {
-        let size: Vector2f = match self.collision_shape {
-            CollisionShape::Circle(circle) => {Vector2f::new(circle.radius*2.0, circle.radius*2.0)}
-            CollisionShape::Rectangle(rectangle) =>{Vector2f::new(rectangle.width, rectangle.height)}
-        };
-        
-        let texture = self.animation_player.get_current_animation_frame();
-        
-        let mut visual_representation = RectangleShape::with_size(size);
-        visual_representation.set_texture(texture, false);
-        visual_representation.set_position(self.position);
-        visual_representation.set_origin(size/2.0);
-        visual_representation.set_scale(Vector2f::new(1.5*self.face_direction as f32, 1.5));
-        visual_representation
+    fn get_visual_size(&self) -> Vector {
+        match self.collision_shape {
+            CollisionShape::Circle(circle) => {Vector::new(circle.radius*2.0, circle.radius*2.0)}
+            CollisionShape::Rectangle(rectangle) =>{Vector::new(rectangle.width, rectangle.height)}
+            _ => {
+                let bounding_box = self.get_collider().bounding_box();
+                bounding_box.max - bounding_box.min
+            }
+        }
     }
 
     fn handle_movement(&mut self, input: &Input) {
-        self.move_direction = Vector::new(0.0, 0.0);
-
-        if input.is_pressed(Keys::Up) {
-            self.move_direction += Vector::new(0.0, -1.0);
-        }
-
-        if input.is_pressed(Keys::Down) {
-            self.move_direction += Vector::new(0.0, 1.0);
-        }
-
-        if input.is_pressed(Keys::Left) {
-            self.move_direction += Vector::new(-1.0, 0.0);
-        }
+        //analog, a half pushed stick walks at half the speed
+        self.move_direction = input.movement_vector();
 
-        if input.is_pressed(Keys::Right) {
-            self.move_direction += Vector::new(1.0, 0.0);
+        //double tapping a direction dashes, a dash isn't cut short by the next one
+        let is_dash_tapped = DASH_KEYS.iter().any(|key| input.double_tapped(*key));
+        if is_dash_tapped && !self.is_dashing() {
+            self.dash_timer.start();
         }
+    }
 
-        self.move_direction = self.move_direction.normal().unwrap_or_default();
+    pub fn is_dashing(&self) -> bool {
+        self.dash_timer.isActive() && !self.dash_timer.isFinished()
     }
 
     fn update_face_direction(&mut self)
     {
-        if(self.move_direction.get_x() > 0.0)
+        if self.move_direction.get_x() > 0.0
         {
             self.face_direction = 1;
         }
-        else if (self.move_direction.get_x() < 0.0) {
+        else if self.move_direction.get_x() < 0.0 {
             self.face_direction = -1;
         }
     }
 
     fn update_position(&mut self, delta_time: f32) {
-        self.position += self.move_direction * self.speed * delta_time;
-    }
-
-    pub fn on_collision(&mut self){
-        self.color = Color::GREEN;
+        let speed = if self.is_dashing() { self.speed * DASH_SPEED_MULTIPLIER } else { self.speed };
+        self.position += self.move_direction * speed * delta_time;
     }
 
     pub fn get_position(&self)->Position{
         self.position
     }
 
-    fn prevent_walking_on_other_objects(&mut self, info: CollisionInfo){
-        self.position = self.position - info.collision_depth;
+    pub fn set_pushable_by_enemies(&mut self, pushable_by_enemies: bool) {
+        self.pushable_by_enemies = pushable_by_enemies;
+    }
+
+    pub fn get_health(&self) -> f32 {
+        self.health
+    }
+
+    pub fn get_max_health(&self) -> f32 {
+        self.max_health
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health <= 0.0
+    }
+
+    /// After being hit the player can't be hit again until the invulnerability timer runs out,
+    /// so touching a crowd doesn't take the whole health in a few frames.
+    fn take_damage(&mut self, damage: f32) {
+        let is_invulnerable = self.invulnerability_timer.isActive() && !self.invulnerability_timer.isFinished();
+        if is_invulnerable {
+            return;
+        }
+
+        self.health = f32::max(self.health - damage, 0.0);
+        self.invulnerability_timer.start();
     }
 }
 
 impl Updatable for Player {
     fn update(&mut self, delta_time: f32) {
+        self.previous_position = self.position;
         self.animation_player.update(delta_time);
+        self.invulnerability_timer.update(delta_time);
+        self.dash_timer.update(delta_time);
 
         self.update_face_direction();
         self.update_position(delta_time);
@@ -112,9 +141,19 @@ impl Updatable for Player {
 }
 
 impl Drawable for Player {
-    fn draw(&mut self, window: &mut RenderWindow) {
-        let visual_representation = self.create_visual_representation();
-        window.draw(&visual_representation);
+    fn draw(&mut self, renderer: &mut dyn Renderer, alpha: f32) {
+        let position = Vector::lerp(self.previous_position, self.position, alpha);
+        let size = self.get_visual_size();
+        let scale = Vector::new(1.5*self.face_direction as f32, 1.5);
+
+        match self.animation_player.get_current_animation_frame() {
+            Some(texture) => renderer.draw_sprite(texture, position, size, scale),
+            //without the animations loaded the player is a plain rectangle
+            None => {
+                let scaled_size = size * 1.5;
+                renderer.draw_rectangle(position - scaled_size * 0.5, scaled_size, self.color);
+            }
+        }
     }
 }
 
@@ -122,27 +161,35 @@ impl Initializable for Player {
     fn initialize(&mut self) {
         let players_animations_path = String::from("resources/Animations/player");
         self.animation_player.initialize(players_animations_path);
+        //the directories are read in no particular order, the last one read would play otherwise
+        self.animation_player.set_current_animation(String::from(PlayersAnimations::Idle.as_str()));
     }
 }
 
 impl Collidable for Player{
+    fn get_id(&self) -> EntityId {
+        self.id
+    }
+
     fn get_collider(&self)-> Collider {
-        Collider { shape: self.collision_shape, position: self.position,  }
+        //enemies have the default priority, so a higher one keeps the crowd from shoving the player
+        let pushback_priority = if self.pushable_by_enemies { 0 } else { 1 };
+        Collider::new(self.collision_shape, self.position)
+            .with_pushback_priority(pushback_priority)
     }
 
     fn get_mask(&self) -> CollisionMask {
         CollisionMask::Player
     }
 
-    fn react_to_collision(&mut self, info: CollisionInfo, other_mask: CollisionMask) {
-        match other_mask {
-            CollisionMask::Player => {
-                self.prevent_walking_on_other_objects(info);
-            },
-            CollisionMask::Enemy => {
-                self.prevent_walking_on_other_objects(info);
-            },
-            CollisionMask::Weapon =>{/* do nothing */}
+    fn translate(&mut self, offset: Vector) {
+        self.position += offset;
+    }
+
+    fn react_to_collision(&mut self, _info: CollisionInfo, other_mask: CollisionMask, _response: CollisionResponse, phase: ContactPhase) {
+        //solid contacts are already resolved by the symulation
+        if other_mask == CollisionMask::Enemy && phase != ContactPhase::Exit {
+            self.take_damage(10.0);
         }
     }
 }
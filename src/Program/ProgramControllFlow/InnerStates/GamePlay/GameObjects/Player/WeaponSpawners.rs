@@ -35,8 +35,10 @@ impl Spawner<SimpleMissile> for SimpleMissileSpawner {
     }
 
     fn spawn(&mut self) -> Vec<SimpleMissile> {
-        let new_simple_missile = SimpleMissile::new(self.spawn_position, self.target_position);
-        vec![new_simple_missile]
+        //with the cursor on the player there is nowhere to shoot
+        SimpleMissile::new(self.spawn_position, self.target_position)
+            .into_iter()
+            .collect()
     }
 
     fn start_spawning(&mut self) {
@@ -0,0 +1,185 @@
+use std::{env, fs, path::Path};
+
+use sfml::graphics::Color;
+
+use crate::{
+    CollisionSystem::Collidable,
+    InputSystem::{
+        Input, InputConsumer, Keys,
+        Replay::{Replay, ReplayFrame},
+    },
+    MathUtilities::Vector,
+    Objects::{
+        Interfaces::{Drawable, Initializable, Updatable},
+        Renderers::RecordingRenderer::{DrawCommand, RecordingRenderer},
+    },
+};
+
+use super::{
+    Enemy::Enemy,
+    Level::{EnemyShape, LevelConfiguration},
+    Player::{Player, WeaponSpawners::SimpleMissileSpawner},
+    Spawner::Spawner,
+    Weapons::SimpleMissile::SimpleMissile,
+};
+
+#[test]
+fn player_without_animations_draws_rectangle_test() {
+    let mut player = Player::new();
+    let mut renderer = RecordingRenderer::new(Vector::new(800.0, 600.0));
+
+    player.draw(&mut renderer, 1.0);
+
+    //the collision circle scaled up like the sprite would be
+    let rectangle = DrawCommand::Rectangle {
+        position: Vector::new(-60.0, -60.0),
+        size: Vector::new(120.0, 120.0),
+        color: Color::RED,
+    };
+    assert_eq!(renderer.get_commands(), &[rectangle]);
+}
+
+#[test]
+fn player_dashes_when_a_direction_is_double_tapped_test() {
+    let mut replay = Replay::new(0);
+    replay.frames = [vec![Keys::Right], vec![], vec![Keys::Right]]
+        .into_iter()
+        .map(|pressed_actions| {
+            let mut frame = ReplayFrame::default();
+            frame.delta_time = 0.1;
+            frame.pressed_actions = pressed_actions;
+            frame.has_focus = true;
+            frame
+        })
+        .collect();
+    let mut input = Input::new();
+    input.initialize();
+    input.start_playback(replay);
+    let mut player = Player::new();
+
+    let mut step = |player: &mut Player| {
+        input.update(0.0);
+        player.handle_input(&input);
+        let start = player.get_position().get_x();
+        player.update(0.1);
+        player.get_position().get_x() - start
+    };
+
+    assert!((step(&mut player) - 20.0).abs() < 1e-3);
+    assert_eq!(step(&mut player), 0.0);
+    assert!(!player.is_dashing());
+    assert!((step(&mut player) - 60.0).abs() < 1e-3);
+    assert!(player.is_dashing());
+}
+
+#[test]
+fn enemy_is_drawn_between_last_two_steps_test() {
+    let mut enemy = Enemy::new(Vector::new(100.0, 0.0));
+    let mut renderer = RecordingRenderer::new(Vector::new(800.0, 600.0));
+
+    enemy.update(1.0);
+    enemy.walk_towards(Vector::new(0.0, 0.0), 1.0);
+    enemy.draw(&mut renderer, 0.5);
+
+    let rectangle = DrawCommand::Rectangle {
+        position: Vector::new(70.0, -20.0),
+        size: Vector::new(40.0, 40.0),
+        color: Color::WHITE,
+    };
+    assert_eq!(renderer.get_commands(), &[rectangle]);
+}
+
+#[test]
+fn enemies_and_player_pass_their_pushing_on_to_colliders_test() {
+    let enemy = Enemy::new(Vector::new(0.0, 0.0)).with_mass(5.0);
+    assert_eq!(enemy.get_collider().mass, 5.0);
+
+    let mut player = Player::new();
+    let enemy_priority = Enemy::new(Vector::new(0.0, 0.0))
+        .get_collider()
+        .pushback_priority;
+    assert!(player.get_collider().pushback_priority > enemy_priority);
+    player.set_pushable_by_enemies(true);
+    assert_eq!(player.get_collider().pushback_priority, enemy_priority);
+}
+
+#[test]
+fn enemy_turns_its_shape_towards_where_it_walks_test() {
+    let capsule = EnemyShape::Capsule {
+        length: 40.0,
+        radius: 10.0,
+    };
+    let mut enemy = Enemy::new(Vector::new(0.0, 0.0)).with_shape(capsule.to_collision_shape());
+    let mut renderer = RecordingRenderer::new(Vector::new(800.0, 600.0));
+
+    enemy.walk_towards(Vector::new(0.0, 100.0), 0.0);
+    assert!((enemy.get_collider().rotation - 90.0).abs() < 1e-3);
+
+    enemy.draw(&mut renderer, 1.0);
+    match renderer.get_commands() {
+        [DrawCommand::Polygon { points, color }] => {
+            assert_eq!(*color, Color::WHITE);
+            //the capsule lies along the y axis once turned
+            let lowest = points.iter().map(|point| point.get_y()).fold(0.0, f32::max);
+            assert!((lowest - 30.0).abs() < 1e-3);
+        }
+        commands => panic!("expected one polygon, got {:?}", commands),
+    }
+}
+
+#[test]
+fn missile_isnt_fired_at_its_start_position_test() {
+    let position = Vector::new(0.0, 0.0);
+    assert!(SimpleMissile::new(position, position).is_none());
+    assert!(SimpleMissile::new(position, Vector::new(10.0, 0.0)).is_some());
+
+    let mut spawner = SimpleMissileSpawner::new(0.2);
+    spawner.set_spawn_position(position);
+    spawner.set_target_position(position);
+    assert!(spawner.spawn().is_empty());
+}
+
+#[test]
+fn broken_levels_are_errors_test() {
+    let path = env::temp_dir().join("vampire_survivors_clone_level_test.json");
+    let load = |json: &str| {
+        fs::write(&path, json).unwrap();
+        LevelConfiguration::load(&path)
+    };
+
+    assert!(load("not json").is_err());
+    assert!(load(r#"{ "name": "empty", "waves": [] }"#).is_err());
+    assert!(load(
+        r#"{ "name": "massless", "waves": [{ "enemy_count": 3, "enemy_spawn_delay_in_seconds": 1.0, "enemy_mass": 0.0 }] }"#
+    )
+    .is_err());
+    assert!(load(
+        r#"{ "name": "line", "waves": [{ "enemy_count": 3, "enemy_spawn_delay_in_seconds": 1.0, "enemy_shape": { "convex_polygon": { "vertices": [[0, 0], [10, 0]] } } }] }"#
+    )
+    .is_err());
+    let concave = load(
+        r#"{ "name": "concave", "waves": [{ "enemy_count": 3, "enemy_spawn_delay_in_seconds": 1.0 }, { "enemy_count": 3, "enemy_spawn_delay_in_seconds": 1.0, "enemy_shape": { "convex_polygon": { "vertices": [[0, 0], [10, 0], [2, 2], [0, 10]] } } }] }"#,
+    );
+    assert!(concave.unwrap_err().contains("wave 2"));
+    let capsules = load(
+        r#"{ "name": "capsules", "waves": [{ "enemy_count": 3, "enemy_spawn_delay_in_seconds": 1.0, "enemy_shape": { "capsule": { "length": 30, "radius": 10 } } }] }"#,
+    );
+    assert_eq!(
+        capsules.unwrap().waves[0].enemy_shape,
+        EnemyShape::Capsule {
+            length: 30.0,
+            radius: 10.0
+        }
+    );
+    let level = load(
+        r#"{ "name": "one", "waves": [{ "enemy_count": 3, "enemy_spawn_delay_in_seconds": 1.0 }] }"#,
+    );
+    fs::remove_file(&path).unwrap();
+
+    let level = level.unwrap();
+    assert_eq!(level.waves.len(), 1);
+    assert_eq!(level.waves[0].enemy_mass, 1.0);
+    assert_eq!(level.waves[0].enemy_shape, EnemyShape::default());
+    assert!(!level.player_pushable_by_enemies);
+    assert!(LevelConfiguration::load(Path::new("resources/DoesNotExist.json")).is_err());
+}
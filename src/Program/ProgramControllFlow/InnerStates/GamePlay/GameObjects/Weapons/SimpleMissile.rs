@@ -1,81 +1,98 @@
-use crate::CollisionSystem::{Collider, CollisionInfo, CollisionMask};
-use sfml::graphics::{CircleShape, Color, RenderTarget, Shape, Transformable};
+use crate::CollisionSystem::{
+    Collider, CollisionInfo, CollisionMask, CollisionResponse, ContactPhase, EntityId,
+};
+use sfml::graphics::Color;
 
 use crate::Objects::Interfaces::Destroyable;
 use crate::{
     CollisionSystem::{Circle, Collidable, CollisionShape},
     MathUtilities::{Position, Vector},
-    Objects::Interfaces::{Drawable, Updatable},
+    Objects::Interfaces::{Drawable, Renderer, Updatable},
 };
 
 pub struct SimpleMissile {
+    id: EntityId,
     start_position: Position,
     position: Position,
-    damage: usize,
+    previous_position: Position,
     speed: f32,
     shape: Circle,
     fly_direction: Vector,
     fly_distance: f32,
+    last_motion: Vector,
     should_be_destroyed: bool,
 }
 
 impl SimpleMissile {
-    pub fn new(start_position: Position, target_position: Position) -> Self {
-        Self {
-            start_position: start_position,
-            position: (start_position),
-            damage: 10,
+    /// How far the missile flies before it is destroyed.
+    pub const FLY_DISTANCE: f32 = 500.0;
+
+    /// None when the target is where the missile starts, it would have no direction to fly in.
+    pub fn new(start_position: Position, target_position: Position) -> Option<Self> {
+        let fly_direction = (target_position - start_position).normal()?;
+
+        Some(Self {
+            id: EntityId::new(),
+            start_position,
+            position: start_position,
+            previous_position: start_position,
             speed: 200.0,
-            fly_direction: (target_position - start_position).normal().unwrap(),
-            fly_distance: 500.0,
+            fly_direction,
+            fly_distance: Self::FLY_DISTANCE,
+            last_motion: Vector::default(),
             shape: Circle { radius: 5.0 },
             should_be_destroyed: false,
-        }
-    }
-
-    pub fn should_be_destroyed(&self) -> bool {
-        self.should_be_destroyed
+        })
     }
 }
 
 impl Updatable for SimpleMissile {
     fn update(&mut self, delta_time: f32) {
+        self.previous_position = self.position;
         if (self.start_position - self.position).length() > self.fly_distance {
             self.should_be_destroyed = true;
         }
 
-        self.position = self.position + self.fly_direction * delta_time * self.speed;
+        self.last_motion = self.fly_direction * delta_time * self.speed;
+        self.position += self.last_motion;
     }
 }
 
 impl Drawable for SimpleMissile {
-    fn draw(&mut self, window: &mut sfml::graphics::RenderWindow) {
-        let mut visual_representation = CircleShape::new(self.shape.radius, 100);
-        visual_representation.set_position(self.position);
-        visual_representation.set_origin(Vector::new(self.shape.radius, self.shape.radius));
-        visual_representation.set_fill_color(Color::RED);
-
-        window.draw(&visual_representation);
+    fn draw(&mut self, renderer: &mut dyn Renderer, alpha: f32) {
+        let position = Vector::lerp(self.previous_position, self.position, alpha);
+        renderer.draw_circle(position, self.shape.radius, Color::RED);
     }
 }
 
 impl Collidable for SimpleMissile {
+    fn get_id(&self) -> EntityId {
+        self.id
+    }
+
     fn get_collider(&self) -> Collider {
-        Collider {
-            shape: CollisionShape::Circle(self.shape),
-            position: self.position,
-        }
+        Collider::new(CollisionShape::Circle(self.shape), self.position)
+            .with_motion(self.last_motion)
     }
 
     fn get_mask(&self) -> CollisionMask {
         CollisionMask::Weapon
     }
 
-    fn react_to_collision(&mut self, info: CollisionInfo, other_mask: CollisionMask) {
-        match other_mask {
-            CollisionMask::Player => { /* do nothing */ }
-            CollisionMask::Weapon => { /* do nothing */ }
-            CollisionMask::Enemy => self.should_be_destroyed = true,
+    fn translate(&mut self, offset: Vector) {
+        self.position += offset;
+    }
+
+    fn react_to_collision(
+        &mut self,
+        _info: CollisionInfo,
+        other_mask: CollisionMask,
+        _response: CollisionResponse,
+        phase: ContactPhase,
+    ) {
+        match (other_mask, phase) {
+            (CollisionMask::Enemy, ContactPhase::Enter) => self.should_be_destroyed = true,
+            _ => { /* do nothing */ }
         }
     }
 }
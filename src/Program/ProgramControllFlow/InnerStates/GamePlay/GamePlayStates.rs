@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod Tests;
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    ControllFlow::{Interfaces::State, FlowState, Transition},
+    InputSystem::{Input, InputConsumer},
+    Objects::Interfaces::{Drawable, Renderer, Updatable},
+};
+
+use super::GameObjects::Level::Level;
+
+use self::InnerStates::{
+    CountdownInnerState, GameOverInnerState, IntermissionInnerState, LevelCompleteInnerState,
+    WaveActiveInnerState,
+};
+
+mod InnerStates;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GameOutcome {
+    Victory,
+    Defeat,
+}
+
+/// What every gameplay sub-state works on, the level lives across all of them.
+#[derive(Clone)]
+pub struct GamePlayContext {
+    pub level: Rc<RefCell<Level>>,
+}
+
+/// Phases of a level, run by a state machine nested in the gameplay state.
+pub enum GamePlayStates {
+    Countdown(CountdownInnerState),
+    WaveActive(WaveActiveInnerState),
+    Intermission(IntermissionInnerState),
+    LevelComplete(LevelCompleteInnerState),
+    GameOver(GameOverInnerState),
+}
+
+impl GamePlayStates {
+    pub fn new(context: GamePlayContext) -> Self {
+        GamePlayStates::Countdown(CountdownInnerState::new(context))
+    }
+
+    /// Set once the player confirmed the end screen of the level.
+    pub fn get_outcome(&self) -> Option<GameOutcome> {
+        match self {
+            GamePlayStates::LevelComplete(level_complete) => level_complete.get_outcome(),
+            GamePlayStates::GameOver(game_over) => game_over.get_outcome(),
+            _ => None,
+        }
+    }
+}
+
+impl State<GamePlayStates> for GamePlayStates {
+    fn onEnter(&mut self) {
+        if let GamePlayStates::WaveActive(wave_active) = self {
+            wave_active.start();
+        }
+    }
+
+    fn onUpdate(&mut self, delta_time: f32) -> FlowState {
+        match self {
+            GamePlayStates::Countdown(countdown) => {
+                countdown.update(delta_time);
+                countdown.get_flow_state()
+            }
+            GamePlayStates::WaveActive(wave_active) => {
+                wave_active.update(delta_time);
+                wave_active.get_flow_state()
+            }
+            GamePlayStates::Intermission(intermission) => {
+                intermission.update(delta_time);
+                intermission.get_flow_state()
+            }
+            //the end screens are left by the outer state machine
+            GamePlayStates::LevelComplete(_) | GamePlayStates::GameOver(_) => FlowState::Update,
+        }
+    }
+
+    fn onExit(&mut self) -> Transition<GamePlayStates> {
+        match self {
+            GamePlayStates::Countdown(countdown) => countdown.get_chosen_transition(),
+            GamePlayStates::WaveActive(wave_active) => wave_active.get_chosen_transition(),
+            GamePlayStates::Intermission(intermission) => intermission.get_chosen_transition(),
+            GamePlayStates::LevelComplete(_) | GamePlayStates::GameOver(_) => Transition::Pop,
+        }
+    }
+}
+
+impl Drawable for GamePlayStates {
+    fn draw(&mut self, renderer: &mut dyn Renderer, alpha: f32) {
+        match self {
+            GamePlayStates::Countdown(countdown) => countdown.draw(renderer, alpha),
+            GamePlayStates::WaveActive(wave_active) => wave_active.draw(renderer, alpha),
+            GamePlayStates::Intermission(intermission) => intermission.draw(renderer, alpha),
+            GamePlayStates::LevelComplete(level_complete) => level_complete.draw(renderer, alpha),
+            GamePlayStates::GameOver(game_over) => game_over.draw(renderer, alpha),
+        }
+    }
+}
+
+impl InputConsumer for GamePlayStates {
+    fn handle_input(&mut self, input: &Input) {
+        match self {
+            GamePlayStates::Countdown(_) => {}
+            GamePlayStates::WaveActive(wave_active) => wave_active.handle_input(input),
+            GamePlayStates::Intermission(intermission) => intermission.handle_input(input),
+            GamePlayStates::LevelComplete(level_complete) => level_complete.handle_input(input),
+            GamePlayStates::GameOver(game_over) => game_over.handle_input(input),
+        }
+    }
+}
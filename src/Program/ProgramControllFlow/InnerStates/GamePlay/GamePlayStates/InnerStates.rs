@@ -0,0 +1,16 @@
+mod Hud;
+
+mod Countdown;
+pub use Countdown::CountdownInnerState;
+
+mod WaveActive;
+pub use WaveActive::WaveActiveInnerState;
+
+mod Intermission;
+pub use Intermission::IntermissionInnerState;
+
+mod LevelComplete;
+pub use LevelComplete::LevelCompleteInnerState;
+
+mod GameOver;
+pub use GameOver::GameOverInnerState;
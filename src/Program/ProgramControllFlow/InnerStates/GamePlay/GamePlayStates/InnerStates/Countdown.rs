@@ -0,0 +1,50 @@
+use crate::{
+    ControllFlow::{FlowState, Transition},
+    Objects::Interfaces::{Drawable, Renderer, Updatable},
+};
+
+use super::super::{GamePlayContext, GamePlayStates};
+use super::{Hud, WaveActiveInnerState};
+
+/// Short break before the first wave, the level is drawn but nothing moves.
+pub struct CountdownInnerState {
+    context: GamePlayContext,
+    remaining_time: f32,
+}
+
+impl CountdownInnerState {
+    pub const DURATION: f32 = 3.0;
+
+    pub fn new(context: GamePlayContext) -> Self {
+        Self {
+            context,
+            remaining_time: Self::DURATION,
+        }
+    }
+
+    pub fn get_flow_state(&self) -> FlowState {
+        if self.remaining_time <= 0.0 {
+            return FlowState::Exit;
+        }
+        FlowState::Update
+    }
+
+    pub fn get_chosen_transition(&mut self) -> Transition<GamePlayStates> {
+        Transition::Replace(GamePlayStates::WaveActive(WaveActiveInnerState::new(self.context.clone())))
+    }
+}
+
+impl Updatable for CountdownInnerState {
+    fn update(&mut self, delta_time: f32) {
+        self.remaining_time -= delta_time;
+    }
+}
+
+impl Drawable for CountdownInnerState {
+    fn draw(&mut self, renderer: &mut dyn Renderer, alpha: f32) {
+        self.context.level.borrow_mut().draw(renderer, alpha);
+
+        let seconds = self.remaining_time.ceil().max(1.0).to_string();
+        Hud::draw_centered_lines(renderer, &["Get ready", &seconds], 40);
+    }
+}
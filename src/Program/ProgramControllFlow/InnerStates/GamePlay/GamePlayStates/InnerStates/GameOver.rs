@@ -0,0 +1,46 @@
+use crate::{
+    InputSystem::{Input, InputConsumer, Keys},
+    Objects::Interfaces::{Drawable, Renderer},
+};
+
+use super::super::{GameOutcome, GamePlayContext};
+use super::Hud;
+
+/// The player died, waits for them to confirm before the results.
+pub struct GameOverInnerState {
+    context: GamePlayContext,
+    is_confirmed: bool,
+}
+
+impl GameOverInnerState {
+    pub fn new(context: GamePlayContext) -> Self {
+        Self {
+            context,
+            is_confirmed: false,
+        }
+    }
+
+    pub fn get_outcome(&self) -> Option<GameOutcome> {
+        if self.is_confirmed {
+            return Some(GameOutcome::Defeat);
+        }
+        None
+    }
+}
+
+impl Drawable for GameOverInnerState {
+    fn draw(&mut self, renderer: &mut dyn Renderer, alpha: f32) {
+        self.context.level.borrow_mut().draw(renderer, alpha);
+
+        Hud::draw_dimmed_background(renderer);
+        Hud::draw_centered_lines(renderer, &["Game over", "Press Enter to continue"], 40);
+    }
+}
+
+impl InputConsumer for GameOverInnerState {
+    fn handle_input(&mut self, input: &Input) {
+        if input.just_pressed(Keys::Enter) {
+            self.is_confirmed = true;
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use sfml::graphics::Color;
+
+use crate::{MathUtilities::Vector, Objects::Interfaces::Renderer};
+
+use super::super::super::GameObjects::Level::Level;
+
+const HEALTH_BAR_WIDTH: f32 = 200.0;
+const HEALTH_BAR_HEIGHT: f32 = 16.0;
+
+/// Wave number and health of the player, in the top left corner.
+pub fn draw_wave_and_health(renderer: &mut dyn Renderer, level: &Level) {
+    let wave_text = format!("Wave {}/{}", level.get_current_wave_number(), level.get_wave_count());
+    renderer.draw_text(&wave_text, 20, Vector::new(20.0, 20.0), Color::WHITE);
+
+    let position = Vector::new(20.0, 55.0);
+    renderer.draw_rectangle(position, Vector::new(HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT), Color::rgb(60, 0, 0));
+
+    let health_size = Vector::new(HEALTH_BAR_WIDTH * level.get_player_health_fraction(), HEALTH_BAR_HEIGHT);
+    renderer.draw_rectangle(position, health_size, Color::RED);
+}
+
+pub fn draw_dimmed_background(renderer: &mut dyn Renderer) {
+    let window_size = renderer.get_size();
+    renderer.draw_rectangle(Vector::default(), window_size, Color::rgba(0, 0, 0, 150));
+}
+
+/// Lines centered around the middle of the window, one below the other.
+pub fn draw_centered_lines(renderer: &mut dyn Renderer, lines: &[&str], character_size: u32) {
+    let window_center = renderer.get_size() * 0.5;
+    let spacing = character_size as f32 * 1.5;
+    let top = window_center.get_y() - spacing * lines.len() as f32 * 0.5;
+
+    for (i, line) in lines.iter().enumerate() {
+        let width = renderer.measure_text(line, character_size).get_x();
+        let position = Vector::new(window_center.get_x() - width * 0.5, top + i as f32 * spacing);
+        renderer.draw_text(line, character_size, position, Color::WHITE);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    ControllFlow::{FlowState, Transition},
+    InputSystem::{Input, InputConsumer, Keys},
+    Objects::Interfaces::{Drawable, Renderer, Updatable},
+};
+
+use super::super::{GamePlayContext, GamePlayStates};
+use super::{GameOverInnerState, Hud, WaveActiveInnerState};
+
+/// Break between two waves. The level keeps running so the player can move around, but nothing
+/// spawns until the break ends or the player skips it.
+pub struct IntermissionInnerState {
+    context: GamePlayContext,
+    remaining_time: f32,
+    should_skip: bool,
+}
+
+impl IntermissionInnerState {
+    pub const DURATION: f32 = 5.0;
+
+    pub fn new(context: GamePlayContext) -> Self {
+        Self {
+            context,
+            remaining_time: Self::DURATION,
+            should_skip: false,
+        }
+    }
+
+    pub fn get_flow_state(&self) -> FlowState {
+        let is_player_dead = self.context.level.borrow().is_player_dead();
+        if is_player_dead || self.should_skip || self.remaining_time <= 0.0 {
+            return FlowState::Exit;
+        }
+        FlowState::Update
+    }
+
+    pub fn get_chosen_transition(&mut self) -> Transition<GamePlayStates> {
+        let context = self.context.clone();
+
+        //enemies left from the last wave can still get the player during the break
+        if self.context.level.borrow().is_player_dead() {
+            return Transition::Replace(GamePlayStates::GameOver(GameOverInnerState::new(context)));
+        }
+
+        Transition::Replace(GamePlayStates::WaveActive(WaveActiveInnerState::new(context)))
+    }
+}
+
+impl Updatable for IntermissionInnerState {
+    fn update(&mut self, delta_time: f32) {
+        self.remaining_time -= delta_time;
+        self.context.level.borrow_mut().update(delta_time);
+    }
+}
+
+impl Drawable for IntermissionInnerState {
+    fn draw(&mut self, renderer: &mut dyn Renderer, alpha: f32) {
+        let mut level = self.context.level.borrow_mut();
+        level.draw(renderer, alpha);
+        Hud::draw_wave_and_health(renderer, &level);
+
+        let countdown = format!("Next wave in {}", self.remaining_time.ceil().max(1.0));
+        Hud::draw_centered_lines(renderer, &[&countdown, "Press Enter to start it now"], 30);
+    }
+}
+
+impl InputConsumer for IntermissionInnerState {
+    fn handle_input(&mut self, input: &Input) {
+        if input.just_pressed(Keys::Enter) {
+            self.should_skip = true;
+        }
+
+        self.context.level.borrow_mut().handle_input(input);
+    }
+}
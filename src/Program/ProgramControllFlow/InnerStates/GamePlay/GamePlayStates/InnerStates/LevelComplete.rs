@@ -0,0 +1,46 @@
+use crate::{
+    InputSystem::{Input, InputConsumer, Keys},
+    Objects::Interfaces::{Drawable, Renderer},
+};
+
+use super::super::{GameOutcome, GamePlayContext};
+use super::Hud;
+
+/// Every wave was cleared, waits for the player to confirm before the results.
+pub struct LevelCompleteInnerState {
+    context: GamePlayContext,
+    is_confirmed: bool,
+}
+
+impl LevelCompleteInnerState {
+    pub fn new(context: GamePlayContext) -> Self {
+        Self {
+            context,
+            is_confirmed: false,
+        }
+    }
+
+    pub fn get_outcome(&self) -> Option<GameOutcome> {
+        if self.is_confirmed {
+            return Some(GameOutcome::Victory);
+        }
+        None
+    }
+}
+
+impl Drawable for LevelCompleteInnerState {
+    fn draw(&mut self, renderer: &mut dyn Renderer, alpha: f32) {
+        self.context.level.borrow_mut().draw(renderer, alpha);
+
+        Hud::draw_dimmed_background(renderer);
+        Hud::draw_centered_lines(renderer, &["Level complete", "Press Enter to continue"], 40);
+    }
+}
+
+impl InputConsumer for LevelCompleteInnerState {
+    fn handle_input(&mut self, input: &Input) {
+        if input.just_pressed(Keys::Enter) {
+            self.is_confirmed = true;
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{
+    ControllFlow::{FlowState, Transition},
+    InputSystem::{Input, InputConsumer},
+    Objects::Interfaces::{Drawable, Renderer, Updatable},
+};
+
+use super::super::{GamePlayContext, GamePlayStates};
+use super::{GameOverInnerState, Hud, IntermissionInnerState, LevelCompleteInnerState};
+
+/// A wave is running, the level updates until the player dies or the wave is cleared.
+pub struct WaveActiveInnerState {
+    context: GamePlayContext,
+}
+
+impl WaveActiveInnerState {
+    pub fn new(context: GamePlayContext) -> Self {
+        Self { context }
+    }
+
+    /// Spawns the next wave, once the state is entered.
+    pub fn start(&mut self) {
+        self.context.level.borrow_mut().start_next_wave();
+    }
+
+    pub fn get_flow_state(&self) -> FlowState {
+        let level = self.context.level.borrow();
+        if level.is_player_dead() || level.is_wave_cleared() {
+            return FlowState::Exit;
+        }
+        FlowState::Update
+    }
+
+    pub fn get_chosen_transition(&mut self) -> Transition<GamePlayStates> {
+        let context = self.context.clone();
+        let level = self.context.level.borrow();
+
+        let state = if level.is_player_dead() {
+            GamePlayStates::GameOver(GameOverInnerState::new(context))
+        } else if level.has_next_wave() {
+            GamePlayStates::Intermission(IntermissionInnerState::new(context))
+        } else {
+            GamePlayStates::LevelComplete(LevelCompleteInnerState::new(context))
+        };
+        Transition::Replace(state)
+    }
+}
+
+impl Updatable for WaveActiveInnerState {
+    fn update(&mut self, delta_time: f32) {
+        self.context.level.borrow_mut().update(delta_time);
+    }
+}
+
+impl Drawable for WaveActiveInnerState {
+    fn draw(&mut self, renderer: &mut dyn Renderer, alpha: f32) {
+        let mut level = self.context.level.borrow_mut();
+        level.draw(renderer, alpha);
+        Hud::draw_wave_and_health(renderer, &level);
+    }
+}
+
+impl InputConsumer for WaveActiveInnerState {
+    fn handle_input(&mut self, input: &Input) {
+        self.context.level.borrow_mut().handle_input(input);
+    }
+}
@@ -0,0 +1,57 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{ControllFlow::StateMachine, Objects::Interfaces::Updatable};
+
+use super::{
+    super::GameObjects::Level::{Level, LevelConfiguration, Wave},
+    GamePlayContext, GamePlayStates,
+    InnerStates::{CountdownInnerState, IntermissionInnerState},
+};
+
+fn create_context(wave_count: usize) -> GamePlayContext {
+    let configuration = LevelConfiguration {
+        name: String::from("empty waves"),
+        waves: vec![Wave::default(); wave_count],
+        player_pushable_by_enemies: false,
+    };
+    GamePlayContext {
+        level: Rc::new(RefCell::new(Level::new_headless(configuration))),
+    }
+}
+
+#[test]
+fn waves_start_when_their_state_is_entered_test() {
+    let context = create_context(2);
+    let get_wave_number = || context.level.borrow().get_current_wave_number();
+    let mut states = StateMachine::with_state(GamePlayStates::new(context.clone()));
+
+    states.update(0.0);
+    states.update(CountdownInnerState::DURATION);
+    assert_eq!(get_wave_number(), 0);
+
+    //the wave is started by entering its state, not by the countdown choosing it
+    states.update(0.0);
+    assert!(matches!(
+        states.get_current_state(),
+        GamePlayStates::WaveActive(_)
+    ));
+    assert_eq!(get_wave_number(), 1);
+
+    //the empty wave is cleared right away and the break before the next one starts
+    for _ in 0..2 {
+        states.update(0.0);
+    }
+    assert!(matches!(
+        states.get_current_state(),
+        GamePlayStates::Intermission(_)
+    ));
+    assert_eq!(get_wave_number(), 1);
+
+    states.update(IntermissionInnerState::DURATION);
+    states.update(0.0);
+    assert!(matches!(
+        states.get_current_state(),
+        GamePlayStates::WaveActive(_)
+    ));
+    assert_eq!(get_wave_number(), 2);
+}
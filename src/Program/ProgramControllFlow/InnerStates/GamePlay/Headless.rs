@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod Tests;
+
+use std::{fmt, path::Path, time::Instant};
+
+use crate::Objects::{
+    Interfaces::Updatable,
+    Profiler::{self, Phase},
+};
+
+use super::GameObjects::Level::{Level, LevelConfiguration};
+
+/// What happened during a headless run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulationSummary {
+    pub simulated_frames: u32,
+    pub time_survived: f32,
+    pub defeated_enemy_count: usize,
+    pub peak_entity_count: usize,
+    pub wave_number: usize,
+    pub wave_count: usize,
+    pub is_player_dead: bool,
+}
+
+impl fmt::Display for SimulationSummary {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(formatter, "simulated frames: {}", self.simulated_frames)?;
+        writeln!(formatter, "time survived: {:.2}s", self.time_survived)?;
+        writeln!(formatter, "player dead: {}", self.is_player_dead)?;
+        writeln!(
+            formatter,
+            "waves reached: {}/{}",
+            self.wave_number, self.wave_count
+        )?;
+        writeln!(formatter, "kills: {}", self.defeated_enemy_count)?;
+        write!(formatter, "peak entity count: {}", self.peak_entity_count)
+    }
+}
+
+/// Runs a level without a window, input or graphics. Waves follow each other right away, there
+/// is nobody to wait for between them.
+pub struct HeadlessSimulation {
+    level: Level,
+    step: f32,
+
+    simulated_frames: u32,
+    peak_entity_count: usize,
+}
+
+impl HeadlessSimulation {
+    /// `step` is the simulated time of one frame, in seconds.
+    pub fn new(level_configuration: LevelConfiguration, step: f32) -> Self {
+        let mut level = Level::new_headless(level_configuration);
+        level.start_next_wave();
+
+        Self {
+            peak_entity_count: level.get_entity_count(),
+            level,
+            step,
+            simulated_frames: 0,
+        }
+    }
+
+    pub fn load(level_path: &Path, step: f32) -> Self {
+        Self::new(LevelConfiguration::from_file(level_path), step)
+    }
+
+    /// The player died or the last wave was cleared.
+    pub fn is_finished(&self) -> bool {
+        self.level.is_player_dead() || (self.level.is_wave_cleared() && !self.level.has_next_wave())
+    }
+
+    pub fn step(&mut self) {
+        let start = Instant::now();
+        if self.level.is_wave_cleared() {
+            self.level.start_next_wave();
+        }
+
+        Profiler::measure(Phase::Update, || self.level.update(self.step));
+        self.simulated_frames += 1;
+        self.peak_entity_count = self.peak_entity_count.max(self.level.get_entity_count());
+        //the real time it took, the simulated time of a frame is always the step
+        Profiler::end_frame(start.elapsed().as_secs_f32());
+    }
+
+    /// Simulates up to `frames` frames, stopping early once the simulation is finished.
+    pub fn run(&mut self, frames: u32) -> SimulationSummary {
+        for _ in 0..frames {
+            if self.is_finished() {
+                break;
+            }
+            self.step();
+        }
+
+        self.get_summary()
+    }
+
+    pub fn get_summary(&self) -> SimulationSummary {
+        SimulationSummary {
+            simulated_frames: self.simulated_frames,
+            time_survived: self.simulated_frames as f32 * self.step,
+            defeated_enemy_count: self.level.get_defeated_enemy_count(),
+            peak_entity_count: self.peak_entity_count,
+            wave_number: self.level.get_current_wave_number(),
+            wave_count: self.level.get_wave_count(),
+            is_player_dead: self.level.is_player_dead(),
+        }
+    }
+}
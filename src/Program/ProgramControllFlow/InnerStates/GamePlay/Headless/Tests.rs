@@ -0,0 +1,44 @@
+use super::{
+    super::GameObjects::Level::{LevelConfiguration, Wave},
+    HeadlessSimulation,
+};
+
+fn create_configuration(enemy_counts: &[usize]) -> LevelConfiguration {
+    LevelConfiguration {
+        name: String::from("Test Level"),
+        waves: enemy_counts
+            .iter()
+            .map(|enemy_count| Wave {
+                enemy_count: *enemy_count,
+                enemy_spawn_delay_in_seconds: 0.1,
+                ..Wave::default()
+            })
+            .collect(),
+        player_pushable_by_enemies: false,
+    }
+}
+
+#[test]
+fn level_runs_without_window_test() {
+    let mut simulation = HeadlessSimulation::new(create_configuration(&[20]), 1.0 / 60.0);
+
+    let summary = simulation.run(120);
+
+    assert_eq!(summary.simulated_frames, 120);
+    assert!((summary.time_survived - 2.0).abs() < 1e-3);
+    assert_eq!(summary.wave_number, 1);
+    //the player and the enemies spawned every tenth of a second
+    assert!(summary.peak_entity_count > 10);
+}
+
+#[test]
+fn cleared_waves_are_followed_by_next_ones_until_last_test() {
+    let mut simulation = HeadlessSimulation::new(create_configuration(&[0, 0, 0]), 1.0 / 60.0);
+
+    let summary = simulation.run(100);
+
+    assert!(simulation.is_finished());
+    assert_eq!(summary.wave_number, 3);
+    assert_eq!(summary.simulated_frames, 2);
+    assert!(!summary.is_player_dead);
+}
@@ -1,8 +1,9 @@
-use sfml::graphics::{Color, RectangleShape, RenderTarget, RenderWindow, Shape, Transformable};
+use sfml::graphics::Color;
 
 use crate::{
     MathUtilities::{Position, Vector},
-    Objects::Interfaces::{Drawable, Updatable},
+    Objects::Interfaces::{Drawable, Renderer, Updatable},
+    Program::ProgramControllFlow::ProgramContext,
 };
 
 
@@ -13,44 +14,43 @@ pub struct MainIntroInnerState {
 
     BAR_SIZE_IN_PERCENT_OF_WINDOW_SIZE: Vector,
     BAR_POSITION_IN_PERCENT_OF_WINDOW_SIZE: Position,
+
+    context: ProgramContext,
 }
 
 
 impl MainIntroInnerState {
-    pub fn new() -> Self {
+    pub fn new(context: ProgramContext) -> Self {
         Self {
             FAKE_LOAD_TIME: 1.0,
             elapsed_time: 0.0,
             progress: 0.0,
             BAR_SIZE_IN_PERCENT_OF_WINDOW_SIZE: Vector::new(0.6, 0.2),
             BAR_POSITION_IN_PERCENT_OF_WINDOW_SIZE: Vector::new(0.5, 0.5),
+            context,
         }
     }
 
-    fn create_bar(&self, window_size: Vector) -> RectangleShape {
-        let mut bar = RectangleShape::new();
+    pub fn get_context(&self) -> ProgramContext {
+        self.context.clone()
+    }
+
+    /// Top left corner and size of the bar.
+    fn get_bar(&self, window_size: Vector) -> (Position, Vector) {
         let bar_size = self.BAR_SIZE_IN_PERCENT_OF_WINDOW_SIZE * window_size;
         let bar_position = self.BAR_POSITION_IN_PERCENT_OF_WINDOW_SIZE * window_size;
-        bar.set_size(bar_size);
-        bar.set_position(bar_position - bar_size * 0.5);
 
-        bar
+        (bar_position - bar_size * 0.5, bar_size)
     }
 
-    fn create_progress_bar_with_current_state(&self, window_size: Vector) -> RectangleShape {
-        let mut progress_bar = self.create_bar(window_size);
-        progress_bar.set_fill_color(Color::RED);
-        progress_bar.set_scale(Vector::new(self.progress, 1.0));
-
-        progress_bar
+    fn draw_progress_bar_with_current_state(&self, renderer: &mut dyn Renderer) {
+        let (position, size) = self.get_bar(renderer.get_size());
+        renderer.draw_rectangle(position, size * Vector::new(self.progress, 1.0), Color::RED);
     }
 
-    fn create_progress_bar_outline(&self, window_size: Vector) -> RectangleShape {
-        let mut progres_bar_outline = self.create_bar(window_size);
-        progres_bar_outline.set_fill_color(Color::TRANSPARENT);
-        progres_bar_outline.set_outline_color(Color::WHITE);
-        progres_bar_outline.set_outline_thickness(30.0);
-        progres_bar_outline
+    fn draw_progress_bar_outline(&self, renderer: &mut dyn Renderer) {
+        let (position, size) = self.get_bar(renderer.get_size());
+        renderer.draw_rectangle_outline(position, size, 30.0, Color::WHITE);
     }
 }
 
@@ -62,12 +62,8 @@ impl Updatable for MainIntroInnerState {
 }
 
 impl Drawable for MainIntroInnerState {
-    fn draw(&mut self, window: &mut RenderWindow) {
-        let window_size = Vector::from_Vector2u(window.size());
-        let progress_bar_outline = self.create_progress_bar_outline(window_size);
-        let progress_bar = self.create_progress_bar_with_current_state(window_size);
-
-        window.draw(&progress_bar_outline);
-        window.draw(&progress_bar);
+    fn draw(&mut self, renderer: &mut dyn Renderer, _alpha: f32) {
+        self.draw_progress_bar_outline(renderer);
+        self.draw_progress_bar_with_current_state(renderer);
     }
 }
\ No newline at end of file
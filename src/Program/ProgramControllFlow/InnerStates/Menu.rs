@@ -1,22 +1,27 @@
+#[cfg(test)]
+mod Tests;
+
 use sfml::{
-    graphics::{Color, Font, RenderTarget, Text, Transformable},
-    window::Key,
-    SfBox,
+    graphics::Color,
+    window::{mouse::{Button, Wheel}, Event},
 };
 
 use crate::{
-    ControllFlow::{FlowState},
+    ControllFlow::{
+        FlowState, Transition,
+        TransitionEffects::{SlideDirection, TransitionEffect},
+    },
     InputSystem::{InputConsumer, Keys},
     MathUtilities::Vector,
-    Objects::Interfaces::{Drawable, Updatable},
-    Program::ProgramControllFlow::States,
+    Objects::Interfaces::{Drawable, Renderer, Updatable},
+    Program::ProgramControllFlow::{ProgramContext, States},
 };
 
-use super::GamePlayInnerState;
+use super::{CreditsInnerState, GamePlayInnerState, OptionsInnerState};
 
-pub struct MainMenuInnerState {
-    font: SfBox<Font>,
+const BUTTON_CHARACTER_SIZE: u32 = 20;
 
+pub struct MainMenuInnerState {
     selections: Vec<String>,
 
     current_selection: i32,
@@ -25,15 +30,19 @@ pub struct MainMenuInnerState {
     start_position: Vector,
     spacing: f32,
 
-    force_close_program: bool,
+    last_mouse_position: Vector,
+    //measured when drawing, to find the entry under the mouse
+    button_sizes: Vec<Vector>,
+
+    context: ProgramContext,
 }
 
 impl MainMenuInnerState {
-    pub fn new() -> Self {
+    pub fn new(context: ProgramContext) -> Self {
         Self {
-            font: Font::from_file("resources/Fonts/gomarice_no_continue.ttf").unwrap(),
             selections: vec![
                 String::from("Play"),
+                String::from("Options"),
                 String::from("Credits"),
                 String::from("Exit"),
             ],
@@ -42,15 +51,31 @@ impl MainMenuInnerState {
 
             start_position: Vector::new(100.0, 100.0),
             spacing: 50.0,
-            force_close_program: false,
+
+            last_mouse_position: Vector::default(),
+            button_sizes: vec![],
+            context,
         }
     }
 
-    pub fn get_chosen_state(&mut self) -> Option<States> {
-        if self.current_selection == 0 {
-            return Some(States::GamePlay(GamePlayInnerState::new()));
+    pub fn get_chosen_transition(&mut self) -> Transition<States> {
+        match self.current_selection {
+            0 => Transition::Replace(States::GamePlay(GamePlayInnerState::new(self.context.clone()))),
+            //the menu stays below the options and the credits, so going back doesn't build it again
+            1 => Transition::Push(States::Options(OptionsInnerState::new(self.context.settings.clone()))),
+            2 => Transition::Push(States::Credits(CreditsInnerState::new())),
+            _ => Transition::Quit,
+        }
+    }
+
+    pub fn get_chosen_transition_effect(&self) -> TransitionEffect {
+        match self.current_selection {
+            0 => TransitionEffect::FadeToBlack { duration: 0.6 },
+            1 => TransitionEffect::Slide { duration: 0.4, direction: SlideDirection::Left },
+            //the credits come up from below, like they scroll
+            2 => TransitionEffect::Slide { duration: 0.4, direction: SlideDirection::Up },
+            _ => TransitionEffect::Cut,
         }
-        None
     }
 
     fn enter_selected_state(&mut self) {
@@ -69,16 +94,6 @@ impl MainMenuInnerState {
         self.should_enter_currently_selected = false;
     }
 
-    fn handle_input(&mut self) {
-        if Key::W.is_pressed() {
-            self.move_up();
-        }
-
-        if Key::S.is_pressed() {
-            self.move_down();
-        }
-    }
-
     fn move_up(&mut self) {
         if self.current_selection > 0 {
             self.current_selection -= 1;
@@ -91,31 +106,81 @@ impl MainMenuInnerState {
         }
     }
 
-    fn draw_menu_buttons(&mut self, window: &mut sfml::graphics::RenderWindow) {
+    fn get_button_position(&self, i: usize) -> Vector {
+        self.start_position + Vector::new(0.0, i as f32 * self.spacing)
+    }
+
+    fn get_button_color(&self, i: usize) -> Color {
+        if i == self.current_selection as usize {
+            Color::RED
+        } else {
+            Color::WHITE
+        }
+    }
+
+    fn draw_menu_buttons(&mut self, renderer: &mut dyn Renderer) {
+        self.button_sizes = self
+            .selections
+            .iter()
+            .map(|selection| renderer.measure_text(selection, BUTTON_CHARACTER_SIZE))
+            .collect();
+
         for i in 0..self.selections.len() {
-            let mut text = Text::new(&self.selections[i], &self.font, 20u32);
-            if i == self.current_selection as usize {
-                text.set_fill_color(Color::RED);
-            } else {
-                text.set_fill_color(Color::WHITE);
-            }
+            let position = self.get_button_position(i);
+            let color = self.get_button_color(i);
+            renderer.draw_text(&self.selections[i], BUTTON_CHARACTER_SIZE, position, color);
+        }
+    }
+
+    fn get_selection_at(&self, position: Vector) -> Option<usize> {
+        self.button_sizes.iter().enumerate().position(|(i, size)| {
+            let offset = position - self.get_button_position(i);
+            (0.0..=size.get_x()).contains(&offset.get_x()) && (0.0..=size.get_y()).contains(&offset.get_y())
+        })
+    }
 
-            let text_position = self.start_position + Vector::new(0.0, i as f32 * self.spacing);
-            text.set_position(text_position);
+    /// Hovering selects the entry under the mouse, but only when the mouse moves so it
+    /// doesn't fight with the keyboard.
+    fn handle_mouse(&mut self, input: &crate::InputSystem::Input) {
+        let mouse_position = input.get_mouse_world_position();
+        let hovered_selection = self.get_selection_at(mouse_position);
 
-            window.draw(&text);
+        let has_mouse_moved = mouse_position.get_x() != self.last_mouse_position.get_x()
+            || mouse_position.get_y() != self.last_mouse_position.get_y();
+        self.last_mouse_position = mouse_position;
+
+        let hovered_selection = match hovered_selection {
+            Some(selection) => selection,
+            None => return,
+        };
+
+        if has_mouse_moved {
+            self.current_selection = hovered_selection as i32;
+        }
+
+        if input.mouse_button_just_pressed(Button::LEFT) {
+            self.current_selection = hovered_selection as i32;
+            self.enter_selected_state();
         }
     }
 
-    fn close_window_if_forced(&mut self, window: &mut sfml::graphics::RenderWindow) {
-        if self.force_close_program {
-            window.close();
+    /// Typing the first letter of an entry selects it.
+    fn select_by_letter(&mut self, letter: char) {
+        let selection = self.selections.iter().position(|selection| {
+            selection
+                .chars()
+                .next()
+                .is_some_and(|first| first.eq_ignore_ascii_case(&letter))
+        });
+
+        if let Some(selection) = selection {
+            self.current_selection = selection as i32;
         }
     }
 }
 
 impl Updatable for MainMenuInnerState {
-    fn update(&mut self, delta_time: f32) {}
+    fn update(&mut self, _delta_time: f32) {}
 }
 
 impl InputConsumer for MainMenuInnerState {
@@ -132,14 +197,26 @@ impl InputConsumer for MainMenuInnerState {
         }
 
         if input.just_pressed(Keys::Esc) {
-            self.force_close_program = true;
+            self.current_selection = (self.selections.len() - 1) as i32;
+            self.enter_selected_state();
+        }
+
+        self.handle_mouse(input);
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        match *event {
+            Event::TextEntered { unicode } => self.select_by_letter(unicode),
+            //scrolling up moves the selection up
+            Event::MouseWheelScrolled { wheel: Wheel::Vertical, delta, .. } if delta > 0.0 => self.move_up(),
+            Event::MouseWheelScrolled { wheel: Wheel::Vertical, delta, .. } if delta < 0.0 => self.move_down(),
+            _ => {}
         }
     }
 }
 
 impl Drawable for MainMenuInnerState {
-    fn draw(&mut self, window: &mut sfml::graphics::RenderWindow) {
-        self.close_window_if_forced(window);
-        self.draw_menu_buttons(window);
+    fn draw(&mut self, renderer: &mut dyn Renderer, _alpha: f32) {
+        self.draw_menu_buttons(renderer);
     }
 }
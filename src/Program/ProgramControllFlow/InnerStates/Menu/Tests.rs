@@ -0,0 +1,97 @@
+use std::path::PathBuf;
+
+use sfml::{
+    graphics::Color,
+    window::{mouse::Wheel, Event},
+};
+
+use crate::{
+    ControllFlow::TransitionEffects::{SlideDirection, TransitionEffect},
+    InputSystem::InputConsumer,
+    MathUtilities::Vector,
+    Objects::{
+        Interfaces::Drawable,
+        Renderers::RecordingRenderer::{DrawCommand, RecordingRenderer},
+    },
+    Program::ProgramControllFlow::{ProgramContext, DEFAULT_LEVEL_PATH},
+    Settings::Settings,
+};
+
+use super::MainMenuInnerState;
+
+fn create_menu() -> MainMenuInnerState {
+    MainMenuInnerState::new(ProgramContext::new(
+        Settings::default(),
+        PathBuf::from(DEFAULT_LEVEL_PATH),
+    ))
+}
+
+fn get_text_color(command: &DrawCommand) -> Color {
+    match command {
+        DrawCommand::Text { color, .. } => *color,
+        _ => panic!("the menu draws only text"),
+    }
+}
+
+#[test]
+fn menu_draws_entries_with_selected_one_highlighted_test() {
+    let mut menu = create_menu();
+    let mut renderer = RecordingRenderer::new(Vector::new(800.0, 600.0));
+
+    menu.draw(&mut renderer, 1.0);
+
+    assert_eq!(
+        renderer.get_texts(),
+        vec!["Play", "Options", "Credits", "Exit"]
+    );
+    let colors: Vec<Color> = renderer.get_commands().iter().map(get_text_color).collect();
+    assert_eq!(
+        colors,
+        vec![Color::RED, Color::WHITE, Color::WHITE, Color::WHITE]
+    );
+}
+
+#[test]
+fn entry_under_mouse_is_found_from_drawn_text_test() {
+    let mut menu = create_menu();
+    let mut renderer = RecordingRenderer::new(Vector::new(800.0, 600.0));
+
+    //nothing can be hovered before the entries are drawn
+    assert_eq!(menu.get_selection_at(Vector::new(105.0, 160.0)), None);
+
+    menu.draw(&mut renderer, 1.0);
+    assert_eq!(menu.get_selection_at(Vector::new(105.0, 160.0)), Some(1));
+    assert_eq!(menu.get_selection_at(Vector::new(400.0, 160.0)), None);
+}
+
+#[test]
+fn menu_slides_to_the_chosen_state_test() {
+    let mut menu = create_menu();
+    let get_slide_direction = |menu: &MainMenuInnerState| match menu.get_chosen_transition_effect()
+    {
+        TransitionEffect::Slide { direction, .. } => direction,
+        effect => panic!("{:?} isn't a slide", effect),
+    };
+
+    menu.current_selection = 1;
+    assert_eq!(get_slide_direction(&menu), SlideDirection::Left);
+    menu.current_selection = 2;
+    assert_eq!(get_slide_direction(&menu), SlideDirection::Up);
+}
+
+#[test]
+fn mouse_wheel_moves_selection_test() {
+    let mut menu = create_menu();
+    let scroll = |delta: f32| Event::MouseWheelScrolled {
+        wheel: Wheel::Vertical,
+        delta,
+        x: 0,
+        y: 0,
+    };
+
+    menu.on_event(&scroll(-1.0));
+    menu.on_event(&scroll(-1.0));
+    assert_eq!(menu.current_selection, 2);
+    menu.on_event(&scroll(1.0));
+    assert_eq!(menu.current_selection, 1);
+}
@@ -0,0 +1,275 @@
+#[cfg(test)]
+mod Tests;
+
+use std::{cell::RefCell, rc::Rc};
+
+use sfml::{
+    graphics::Color,
+    window::{Event, Key},
+};
+
+use crate::{
+    ControllFlow::FlowState,
+    InputSystem::{Input, InputConsumer, InputContext, KeyBindings::key_name, Keys},
+    MathUtilities::Vector,
+    Objects::Interfaces::{Drawable, Renderer, Updatable},
+    Settings::Settings,
+};
+
+const ENTRY_CHARACTER_SIZE: u32 = 20;
+//holding left or right keeps changing the value, after the delay once every interval
+const HOLD_REPEAT_DELAY: f32 = 0.4;
+const HOLD_REPEAT_INTERVAL: f32 = 0.1;
+
+//gameplay actions the player can rebind from the menu
+const REBINDABLE_ACTIONS: [(Keys, &str); 5] = [
+    (Keys::Up, "Move up"),
+    (Keys::Down, "Move down"),
+    (Keys::Left, "Move left"),
+    (Keys::Right, "Move right"),
+    (Keys::ToggleAim, "Toggle aim"),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum OptionsEntry {
+    Resolution,
+    Fullscreen,
+    VerticalSync,
+    FrameRateLimit,
+    Volume,
+    Binding(Keys, &'static str),
+    Back,
+}
+
+/// Edits the settings, every change is applied and saved right away.
+pub struct OptionsInnerState {
+    settings: Settings,
+    //handed back to the program after every change
+    shared_settings: Rc<RefCell<Settings>>,
+    entries: Vec<OptionsEntry>,
+
+    current_selection: usize,
+    is_waiting_for_key: bool,
+    //the key that was just bound shouldn't also move the selection
+    has_just_bound_key: bool,
+    should_go_back: bool,
+    //how long the key has to be held for the next repeated change
+    next_repeat_time: f32,
+
+    start_position: Vector,
+    spacing: f32,
+}
+
+impl OptionsInnerState {
+    pub fn new(shared_settings: Rc<RefCell<Settings>>) -> Self {
+        let mut entries = vec![
+            OptionsEntry::Resolution,
+            OptionsEntry::Fullscreen,
+            OptionsEntry::VerticalSync,
+            OptionsEntry::FrameRateLimit,
+            OptionsEntry::Volume,
+        ];
+        entries.extend(
+            REBINDABLE_ACTIONS
+                .iter()
+                .map(|(action, name)| OptionsEntry::Binding(*action, name)),
+        );
+        entries.push(OptionsEntry::Back);
+        let settings = shared_settings.borrow().clone();
+
+        Self {
+            settings,
+            shared_settings,
+            entries,
+            current_selection: 0,
+            is_waiting_for_key: false,
+            has_just_bound_key: false,
+            should_go_back: false,
+            next_repeat_time: HOLD_REPEAT_DELAY,
+            start_position: Vector::new(100.0, 60.0),
+            spacing: 40.0,
+        }
+    }
+
+    pub fn get_flow_state(&self) -> FlowState {
+        if self.should_go_back {
+            return FlowState::Exit;
+        }
+        FlowState::Update
+    }
+
+    fn get_selected_entry(&self) -> OptionsEntry {
+        self.entries[self.current_selection]
+    }
+
+    fn apply(&mut self) {
+        *self.shared_settings.borrow_mut() = self.settings.clone();
+    }
+
+    fn change_selected(&mut self, step: i32) {
+        match self.get_selected_entry() {
+            OptionsEntry::Resolution => self.settings.cycle_resolution(step),
+            OptionsEntry::Fullscreen => self.settings.fullscreen = !self.settings.fullscreen,
+            OptionsEntry::VerticalSync => self.settings.vsync = !self.settings.vsync,
+            OptionsEntry::FrameRateLimit => self.settings.cycle_frame_rate_limit(step),
+            OptionsEntry::Volume => self.settings.change_volume(step),
+            OptionsEntry::Binding(..) | OptionsEntry::Back => return,
+        }
+        self.apply();
+    }
+
+    //toggles and window changes aren't repeated, they would flicker while the key is held
+    fn is_selected_repeatable(&self) -> bool {
+        matches!(
+            self.get_selected_entry(),
+            OptionsEntry::FrameRateLimit | OptionsEntry::Volume
+        )
+    }
+
+    fn handle_value_change(&mut self, input: &Input, key: Keys, step: i32) {
+        if input.just_pressed(key) {
+            self.change_selected(step);
+        } else if self.is_selected_repeatable() && input.is_held_for(key, self.next_repeat_time) {
+            self.change_selected(step);
+            self.next_repeat_time += HOLD_REPEAT_INTERVAL;
+        }
+
+        if input.just_released(key) {
+            self.next_repeat_time = HOLD_REPEAT_DELAY;
+        }
+    }
+
+    fn enter_selected(&mut self) {
+        match self.get_selected_entry() {
+            OptionsEntry::Binding(..) => self.is_waiting_for_key = true,
+            OptionsEntry::Back => self.should_go_back = true,
+            _ => self.change_selected(1),
+        }
+    }
+
+    fn bind_selected(&mut self, key: Key) {
+        self.is_waiting_for_key = false;
+        self.has_just_bound_key = true;
+
+        //escape only cancels, otherwise there would be no way out of the menu without a binding
+        if key == Key::ESCAPE {
+            return;
+        }
+        //a key without a name couldn't be saved, sfml reports some keys as unknown
+        if key_name(key).is_none() {
+            return;
+        }
+        if let OptionsEntry::Binding(action, _) = self.get_selected_entry() {
+            //a bound key is taken off the action, unless it is the last way to trigger it
+            let key_bindings = &mut self.settings.key_bindings;
+            let keys = key_bindings.get_keys(InputContext::Gameplay, action);
+            if !keys.contains(&key) {
+                key_bindings.add_key(InputContext::Gameplay, action, key);
+            } else if keys.len() > 1 {
+                key_bindings.remove_key(InputContext::Gameplay, action, key);
+            }
+            self.apply();
+        }
+    }
+
+    fn get_entry_text(&self, entry: OptionsEntry) -> String {
+        let on_off = |value: bool| if value { "on" } else { "off" };
+
+        match entry {
+            OptionsEntry::Resolution => {
+                let (width, height) = self.settings.resolution;
+                format!("Resolution: {}x{}", width, height)
+            }
+            OptionsEntry::Fullscreen => format!("Fullscreen: {}", on_off(self.settings.fullscreen)),
+            OptionsEntry::VerticalSync => format!("Vertical sync: {}", on_off(self.settings.vsync)),
+            OptionsEntry::FrameRateLimit => match self.settings.frame_rate_limit {
+                0 => String::from("Frame rate limit: none"),
+                limit => format!("Frame rate limit: {}", limit),
+            },
+            OptionsEntry::Volume => format!("Volume: {}", self.settings.volume),
+            OptionsEntry::Binding(_, name)
+                if self.is_waiting_for_key && entry == self.get_selected_entry() =>
+            {
+                format!("{}: press a key to add or remove", name)
+            }
+            OptionsEntry::Binding(action, name) => {
+                let keys: Vec<&str> = self
+                    .settings
+                    .key_bindings
+                    .get_keys(InputContext::Gameplay, action)
+                    .iter()
+                    .filter_map(|key| key_name(*key))
+                    .collect();
+                format!("{}: {}", name, keys.join(", "))
+            }
+            OptionsEntry::Back => String::from("Back"),
+        }
+    }
+
+    fn move_selection(&mut self, step: i32) {
+        let last = self.entries.len() as i32 - 1;
+        self.current_selection = (self.current_selection as i32 + step).clamp(0, last) as usize;
+    }
+}
+
+impl Updatable for OptionsInnerState {
+    fn update(&mut self, _delta_time: f32) {}
+}
+
+impl InputConsumer for OptionsInnerState {
+    fn handle_input(&mut self, input: &Input) {
+        if self.is_waiting_for_key {
+            return;
+        }
+        if self.has_just_bound_key {
+            self.has_just_bound_key = false;
+            return;
+        }
+
+        if input.just_pressed(Keys::Down) {
+            self.move_selection(1);
+        }
+        if input.just_pressed(Keys::Up) {
+            self.move_selection(-1);
+        }
+        self.handle_value_change(input, Keys::Right, 1);
+        self.handle_value_change(input, Keys::Left, -1);
+
+        if input.just_pressed(Keys::Enter) {
+            self.enter_selected();
+        }
+        if input.just_pressed(Keys::Esc) {
+            self.should_go_back = true;
+        }
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        match *event {
+            Event::KeyPressed { code, .. } if self.is_waiting_for_key => self.bind_selected(code),
+            _ => {}
+        }
+    }
+
+    fn get_input_context(&self) -> InputContext {
+        InputContext::Menu
+    }
+}
+
+impl Drawable for OptionsInnerState {
+    fn draw(&mut self, renderer: &mut dyn Renderer, _alpha: f32) {
+        for (i, entry) in self.entries.iter().enumerate() {
+            let position = self.start_position + Vector::new(0.0, i as f32 * self.spacing);
+            let color = if i == self.current_selection {
+                Color::RED
+            } else {
+                Color::WHITE
+            };
+            renderer.draw_text(
+                &self.get_entry_text(*entry),
+                ENTRY_CHARACTER_SIZE,
+                position,
+                color,
+            );
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use std::{cell::RefCell, rc::Rc};
+
+use sfml::window::{Event, Key};
+
+use crate::{
+    InputSystem::{
+        Input, InputConsumer, InputContext, Keys,
+        Replay::{Replay, ReplayFrame},
+    },
+    MathUtilities::Vector,
+    Objects::{
+        Interfaces::{Drawable, Initializable, Updatable},
+        Renderers::RecordingRenderer::RecordingRenderer,
+    },
+    Settings::Settings,
+};
+
+use super::{OptionsEntry, OptionsInnerState};
+
+fn key_pressed(code: Key) -> Event {
+    Event::KeyPressed {
+        code,
+        alt: false,
+        ctrl: false,
+        shift: false,
+        system: false,
+    }
+}
+
+fn create_options() -> OptionsInnerState {
+    OptionsInnerState::new(Rc::new(RefCell::new(Settings::default())))
+}
+
+fn select(options: &mut OptionsInnerState, entry: OptionsEntry) {
+    options.current_selection = options
+        .entries
+        .iter()
+        .position(|other| *other == entry)
+        .unwrap();
+}
+
+#[test]
+fn options_show_current_values_test() {
+    let mut options = create_options();
+    let mut renderer = RecordingRenderer::new(Vector::new(800.0, 600.0));
+
+    options.draw(&mut renderer, 1.0);
+
+    let texts = renderer.get_texts();
+    assert_eq!(
+        texts[..5],
+        [
+            "Resolution: 1280x720",
+            "Fullscreen: off",
+            "Vertical sync: off",
+            "Frame rate limit: none",
+            "Volume: 100"
+        ]
+    );
+    assert_eq!(texts[5], "Move up: W, Up");
+    assert_eq!(*texts.last().unwrap(), "Back");
+}
+
+#[test]
+fn options_edits_are_applied_and_keys_rebound_test() {
+    let shared_settings = Rc::new(RefCell::new(Settings::default()));
+    let mut options = OptionsInnerState::new(shared_settings.clone());
+
+    select(&mut options, OptionsEntry::Volume);
+    options.change_selected(-1);
+    assert_eq!(shared_settings.borrow().volume, 90.0);
+
+    select(
+        &mut options,
+        OptionsEntry::Binding(Keys::ToggleAim, "Toggle aim"),
+    );
+    options.enter_selected();
+    assert_eq!(
+        options.get_entry_text(options.get_selected_entry()),
+        "Toggle aim: press a key to add or remove"
+    );
+
+    //escape cancels instead of being bound
+    options.on_event(&key_pressed(Key::ESCAPE));
+    assert!(!options.is_waiting_for_key);
+    assert_eq!(
+        options
+            .settings
+            .key_bindings
+            .get_keys(InputContext::Gameplay, Keys::ToggleAim),
+        &[Key::T]
+    );
+
+    let get_keys = || {
+        shared_settings
+            .borrow()
+            .key_bindings
+            .get_keys(InputContext::Gameplay, Keys::ToggleAim)
+            .to_vec()
+    };
+    options.enter_selected();
+    options.on_event(&key_pressed(Key::R));
+    assert_eq!(get_keys(), [Key::T, Key::R]);
+
+    //pressing a bound key again takes it off, but never the last one
+    options.enter_selected();
+    options.on_event(&key_pressed(Key::T));
+    assert_eq!(get_keys(), [Key::R]);
+    options.enter_selected();
+    options.on_event(&key_pressed(Key::R));
+    assert_eq!(get_keys(), [Key::R]);
+
+    //keys sfml can't name aren't bound
+    options.enter_selected();
+    options.on_event(&key_pressed(Key::UNKNOWN));
+    assert_eq!(get_keys(), [Key::R]);
+}
+
+#[test]
+fn options_repeat_changes_while_a_key_is_held_test() {
+    let mut options = create_options();
+    options.settings.volume = 0.0;
+    select(&mut options, OptionsEntry::Volume);
+
+    let mut replay = Replay::new(0);
+    let held_frames = vec![(0.2, true); 4];
+    replay.frames = held_frames
+        .into_iter()
+        .chain([(0.2, false), (0.2, true), (0.4, true), (0.4, true)])
+        .map(|(delta_time, is_held)| {
+            let mut frame = ReplayFrame::default();
+            frame.delta_time = delta_time;
+            frame.pressed_actions = if is_held { vec![Keys::Right] } else { vec![] };
+            frame.has_focus = true;
+            frame
+        })
+        .collect();
+    let mut input = Input::new();
+    input.initialize();
+    input.start_playback(replay);
+
+    let volumes: Vec<f32> = (0..6)
+        .map(|_| {
+            input.update(0.0);
+            options.handle_input(&input);
+            options.settings.volume
+        })
+        .collect();
+
+    //pressed, held for 0.2, for 0.4 and for 0.6, released and pressed again
+    assert_eq!(volumes, [10.0, 10.0, 20.0, 30.0, 30.0, 40.0]);
+
+    //toggles only change once per press
+    select(&mut options, OptionsEntry::Fullscreen);
+    let fullscreen = options.settings.fullscreen;
+    for _ in 0..2 {
+        input.update(0.0);
+        options.handle_input(&input);
+    }
+    assert_eq!(options.settings.fullscreen, fullscreen);
+}
@@ -0,0 +1,55 @@
+use sfml::graphics::Color;
+
+use crate::{
+    ControllFlow::FlowState,
+    InputSystem::{Input, InputConsumer, InputContext, Keys},
+    MathUtilities::Vector,
+    Objects::Interfaces::{Drawable, Renderer, Updatable},
+};
+
+/// Overlay stopping the gameplay below it until the player continues.
+pub struct PauseInnerState {
+    should_resume: bool,
+}
+
+impl PauseInnerState {
+    pub fn new() -> Self {
+        Self {
+            should_resume: false,
+        }
+    }
+
+    pub fn get_flow_state(&self) -> FlowState {
+        if self.should_resume {
+            return FlowState::Exit;
+        }
+        FlowState::Update
+    }
+}
+
+impl Updatable for PauseInnerState {
+    fn update(&mut self, _delta_time: f32) {}
+}
+
+impl Drawable for PauseInnerState {
+    fn draw(&mut self, renderer: &mut dyn Renderer, _alpha: f32) {
+        let window_size = renderer.get_size();
+        renderer.draw_rectangle(Vector::default(), window_size, Color::rgba(0, 0, 0, 150));
+
+        let text_position = window_size * 0.5 - Vector::new(250.0, 15.0);
+        renderer.draw_text("Paused - press Enter to continue", 30, text_position, Color::WHITE);
+    }
+}
+
+impl InputConsumer for PauseInnerState {
+    fn handle_input(&mut self, input: &Input) {
+        //resuming is left to the player, the game shouldn't start running behind their back
+        if input.just_pressed(Keys::Enter) || input.just_pressed(Keys::Esc) {
+            self.should_resume = true;
+        }
+    }
+
+    fn get_input_context(&self) -> InputContext {
+        InputContext::Menu
+    }
+}
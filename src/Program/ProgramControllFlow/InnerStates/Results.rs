@@ -0,0 +1,79 @@
+use sfml::graphics::Color;
+
+use crate::{
+    ControllFlow::FlowState,
+    InputSystem::{Input, InputConsumer, Keys},
+    MathUtilities::Vector,
+    Objects::Interfaces::{Drawable, Renderer, Updatable},
+    Program::ProgramControllFlow::ProgramContext,
+};
+
+use super::{GameOutcome, GameResults};
+
+pub struct ResultsInnerState {
+    results: GameResults,
+    should_go_back: bool,
+
+    start_position: Vector,
+    spacing: f32,
+
+    context: ProgramContext,
+}
+
+impl ResultsInnerState {
+    pub fn new(results: GameResults, context: ProgramContext) -> Self {
+        Self {
+            results,
+            should_go_back: false,
+            start_position: Vector::new(100.0, 100.0),
+            spacing: 50.0,
+            context,
+        }
+    }
+
+    pub fn get_context(&self) -> ProgramContext {
+        self.context.clone()
+    }
+
+    pub fn get_flow_state(&self) -> FlowState {
+        if self.should_go_back {
+            return FlowState::Exit;
+        }
+        FlowState::Update
+    }
+
+    fn get_lines(&self) -> Vec<String> {
+        let title = match self.results.outcome {
+            GameOutcome::Victory => "Victory",
+            GameOutcome::Defeat => "Defeat",
+        };
+
+        vec![
+            String::from(title),
+            format!("Waves reached: {}/{}", self.results.wave_number, self.results.wave_count),
+            format!("Enemies defeated: {}", self.results.defeated_enemy_count),
+            String::from("Press Enter to go back to the menu"),
+        ]
+    }
+}
+
+impl Updatable for ResultsInnerState {
+    fn update(&mut self, _delta_time: f32) {}
+}
+
+impl Drawable for ResultsInnerState {
+    fn draw(&mut self, renderer: &mut dyn Renderer, _alpha: f32) {
+        for (i, line) in self.get_lines().iter().enumerate() {
+            let position = self.start_position + Vector::new(0.0, i as f32 * self.spacing);
+            renderer.draw_text(line, 20, position, Color::WHITE);
+        }
+    }
+}
+
+impl InputConsumer for ResultsInnerState {
+    fn handle_input(&mut self, input: &Input) {
+        if input.just_pressed(Keys::Enter) || input.just_pressed(Keys::Esc) {
+            self.should_go_back = true;
+        }
+    }
+}
@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod Tests;
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    InputSystem::KeyBindings::{KeyBindings, KEY_BINDINGS_PATH},
+    Objects::Log,
+};
+
+const SETTINGS_DIRECTORY_NAME: &str = "vampire_survivors_clone";
+const SETTINGS_FILE_NAME: &str = "Settings.json";
+
+pub const RESOLUTIONS: [(u32, u32); 4] = [(1280, 720), (1600, 900), (1920, 1080), (2560, 1440)];
+//0 leaves the frame rate unlimited
+pub const FRAME_RATE_LIMITS: [u32; 5] = [0, 30, 60, 120, 144];
+pub const VOLUME_STEP: f32 = 10.0;
+
+/// What the player can change about the game, saved between runs.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub resolution: (u32, u32),
+    pub fullscreen: bool,
+    pub vsync: bool,
+    pub frame_rate_limit: u32,
+    //from 0 to 100
+    pub volume: f32,
+    pub key_bindings: KeyBindings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            resolution: RESOLUTIONS[0],
+            fullscreen: false,
+            vsync: false,
+            frame_rate_limit: 0,
+            volume: 100.0,
+            key_bindings: KeyBindings::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Values missing from the file, or the whole file when it can't be read, keep the defaults.
+    pub fn load(path: &Path) -> Self {
+        let json = match fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(_) => return Self::with_shipped_key_bindings(),
+        };
+
+        Self::from_json(&json).unwrap_or_else(|error| {
+            Log::warn(&format!(
+                "couldn't read the settings {}: {}",
+                path.display(),
+                error
+            ));
+            Self::with_shipped_key_bindings()
+        })
+    }
+
+    //the bindings shipped with the game, until the player changes them
+    fn with_shipped_key_bindings() -> Self {
+        Self {
+            key_bindings: KeyBindings::load(Path::new(KEY_BINDINGS_PATH)),
+            ..Self::default()
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, self.to_json())
+    }
+
+    /// Next or previous of the offered resolutions, wrapping around.
+    pub fn cycle_resolution(&mut self, step: i32) {
+        self.resolution = cycle(&RESOLUTIONS, self.resolution, step);
+    }
+
+    pub fn cycle_frame_rate_limit(&mut self, step: i32) {
+        self.frame_rate_limit = cycle(&FRAME_RATE_LIMITS, self.frame_rate_limit, step);
+    }
+
+    pub fn change_volume(&mut self, step: i32) {
+        self.volume = (self.volume + step as f32 * VOLUME_STEP).clamp(0.0, 100.0);
+    }
+}
+
+/// A value that isn't one of the offered ones, like a resolution from the command line,
+/// goes to the first or the last of them.
+fn cycle<T: Copy + PartialEq>(values: &[T], current: T, step: i32) -> T {
+    let count = values.len() as i32;
+    let index = match values.iter().position(|value| *value == current) {
+        Some(index) => (index as i32 + step).rem_euclid(count),
+        None if step < 0 => count - 1,
+        None => 0,
+    };
+    values[index as usize]
+}
+
+/// The settings are the player's, not the game's, so they are kept in the user configuration
+/// directory, `$XDG_CONFIG_HOME`, `%APPDATA%` or `~/.config`, rather than with the resources.
+/// Without any of them they are kept in the working directory.
+pub fn get_settings_path() -> PathBuf {
+    let configuration_directory = env::var_os("XDG_CONFIG_HOME")
+        .or_else(|| env::var_os("APPDATA"))
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+
+    match configuration_directory {
+        Some(directory) => directory
+            .join(SETTINGS_DIRECTORY_NAME)
+            .join(SETTINGS_FILE_NAME),
+        None => PathBuf::from(SETTINGS_FILE_NAME),
+    }
+}
+
+/// Window options given on the command line, they are used for this run only and never saved.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WindowOverrides {
+    pub resolution: Option<(u32, u32)>,
+    pub fullscreen: Option<bool>,
+}
+
+impl WindowOverrides {
+    pub fn get_resolution(&self, settings: &Settings) -> (u32, u32) {
+        self.resolution.unwrap_or(settings.resolution)
+    }
+
+    pub fn is_fullscreen(&self, settings: &Settings) -> bool {
+        self.fullscreen.unwrap_or(settings.fullscreen)
+    }
+
+    /// Once the player picks a value in the options, it is used instead of the command line.
+    pub fn drop_changed(&mut self, previous: &Settings, current: &Settings) {
+        if previous.resolution != current.resolution {
+            self.resolution = None;
+        }
+        if previous.fullscreen != current.fullscreen {
+            self.fullscreen = None;
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::{env, fs, path::Path};
+
+use sfml::window::Key;
+
+use crate::InputSystem::{InputContext, Keys};
+
+use super::{get_settings_path, Settings, WindowOverrides, RESOLUTIONS};
+
+#[test]
+fn settings_save_and_load_test() {
+    let path = env::temp_dir().join("vampire_survivors_clone_settings_test.json");
+    let mut settings = Settings {
+        resolution: (1920, 1080),
+        fullscreen: true,
+        vsync: true,
+        frame_rate_limit: 60,
+        volume: 40.0,
+        ..Settings::default()
+    };
+    settings
+        .key_bindings
+        .rebind(InputContext::Gameplay, Keys::ToggleAim, vec![Key::R]);
+
+    settings.save(&path).unwrap();
+    let loaded = Settings::load(&path);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, settings);
+}
+
+#[test]
+fn settings_missing_values_keep_defaults_test() {
+    let settings = Settings::from_json(
+        r#"{ "volume": 25.0, "key_bindings": { "menu": { "enter": ["E"] } } }"#,
+    )
+    .unwrap();
+
+    assert_eq!(settings.volume, 25.0);
+    assert_eq!(settings.resolution, Settings::default().resolution);
+    assert_eq!(
+        settings
+            .key_bindings
+            .get_keys(InputContext::Menu, Keys::Enter),
+        &[Key::E]
+    );
+    assert_eq!(
+        settings.key_bindings.get_keys(InputContext::Menu, Keys::Up),
+        Settings::default()
+            .key_bindings
+            .get_keys(InputContext::Menu, Keys::Up)
+    );
+}
+
+#[test]
+fn settings_unreadable_file_uses_defaults_test() {
+    assert_eq!(
+        Settings::load(Path::new("resources/DoesNotExist.json")),
+        Settings::default()
+    );
+    assert!(Settings::from_json("not json").is_err());
+}
+
+#[test]
+fn settings_values_cycle_test() {
+    let mut settings = Settings::default();
+
+    settings.cycle_resolution(-1);
+    assert_eq!(settings.resolution, RESOLUTIONS[RESOLUTIONS.len() - 1]);
+    settings.cycle_resolution(1);
+    assert_eq!(settings.resolution, RESOLUTIONS[0]);
+
+    //a resolution from the command line isn't one of the offered ones
+    settings.resolution = (800, 600);
+    settings.cycle_resolution(1);
+    assert_eq!(settings.resolution, RESOLUTIONS[0]);
+
+    settings.cycle_frame_rate_limit(1);
+    assert_eq!(settings.frame_rate_limit, 30);
+
+    settings.change_volume(1);
+    assert_eq!(settings.volume, 100.0);
+    settings.change_volume(-3);
+    assert_eq!(settings.volume, 70.0);
+}
+
+#[test]
+fn window_overrides_last_until_changed_in_options_test() {
+    let mut overrides = WindowOverrides {
+        resolution: Some((800, 600)),
+        fullscreen: Some(true),
+    };
+    let saved = Settings::default();
+
+    assert_eq!(overrides.get_resolution(&saved), (800, 600));
+    assert!(overrides.is_fullscreen(&saved));
+
+    let mut edited = saved.clone();
+    edited.vsync = true;
+    overrides.drop_changed(&saved, &edited);
+    assert_eq!(overrides.get_resolution(&edited), (800, 600));
+
+    edited.cycle_resolution(1);
+    overrides.drop_changed(&saved, &edited);
+    assert_eq!(overrides.get_resolution(&edited), RESOLUTIONS[1]);
+    assert!(overrides.is_fullscreen(&edited));
+}
+
+#[test]
+fn settings_are_kept_outside_resources_test() {
+    let path = get_settings_path();
+
+    assert!(path.ends_with("Settings.json"));
+    assert!(!path.starts_with("resources"));
+}
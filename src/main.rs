@@ -15,12 +15,103 @@ mod ControllFlow;
 
 #[allow(non_snake_case)]
 mod Program;
-use Program::Program as MainProgram;
+use Program::{HeadlessSimulation, Program as MainProgram};
 
+#[allow(non_snake_case)]
+mod LaunchOptions;
+use LaunchOptions::{LaunchOptions as Options, USAGE};
+
+#[allow(non_snake_case)]
+mod Settings;
+use Settings::{Settings as UserSettings, WindowOverrides};
+
+use std::{env, path::{Path, PathBuf}, process};
+
+use Objects::{Log, Profiler, Random};
 
 fn main() {
-    let mut program = MainProgram::new();
+    let arguments: Vec<String> = env::args().skip(1).collect();
+    if arguments.iter().any(|argument| argument == "--help") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let options = match Options::parse(&arguments) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}\n{}", error, USAGE);
+            process::exit(2);
+        }
+    };
+    Log::set_level(options.log_level);
+
+    let level_path = options.level_path.clone().unwrap_or_else(|| PathBuf::from(Program::DEFAULT_LEVEL_PATH));
+    if let Err(error) = Program::check_level(&level_path) {
+        Log::error(&error);
+        process::exit(1);
+    }
+
+    if let Some(path) = &options.profile_path {
+        if let Err(error) = Profiler::with_profiler(|profiler| profiler.start_csv(path)) {
+            Log::error(&format!("couldn't create {}: {}", path.display(), error));
+            process::exit(1);
+        }
+    }
+
+    if options.headless {
+        run_headless(&options, &level_path);
+        return;
+    }
+
+    let settings_path = Settings::get_settings_path();
+    let settings = UserSettings::load(&settings_path);
+
+    //the command line only overrides the saved settings for this run
+    let window_overrides = WindowOverrides {
+        resolution: options.resolution,
+        fullscreen: options.fullscreen.then_some(true),
+    };
+    let mut program = MainProgram::new(settings, &settings_path, &level_path, window_overrides);
 
     program.initialize();
+    program.set_simulation_rate(options.simulation_rate);
+    if let Some(seed) = options.seed {
+        Random::set_seed(seed);
+    }
+    if options.skip_intro {
+        program.skip_intro();
+    }
+    if let Some(path) = &options.record_path {
+        program.record_to(path);
+    }
+    if let Some(path) = &options.replay_path {
+        if options.seed.is_some() {
+            Log::warn("the replay is played with the seed it was recorded with, not --seed");
+        }
+        if let Err(error) = program.replay_from(path) {
+            Log::error(&format!("couldn't load the replay {}: {}", path.display(), error));
+            process::exit(1);
+        }
+    }
+    Log::info(&format!("seed {}", Random::get_seed()));
+
     program.main_loop();
 }
+
+/// Simulates the level without opening a window and prints what happened.
+fn run_headless(options: &Options, level_path: &Path) {
+    //nothing reads the input without a window, so a run couldn't be recorded or played back
+    if options.record_path.is_some() || options.replay_path.is_some() {
+        Log::warn("--record and --replay are ignored with --headless");
+    }
+
+    Random::set_seed(options.seed.unwrap_or_else(Random::random_seed));
+    let mut simulation = HeadlessSimulation::load(level_path, 1.0 / options.simulation_rate);
+    let summary = simulation.run(options.frames);
+    if let Err(error) = Profiler::with_profiler(|profiler| profiler.stop_csv()) {
+        Log::error(&format!("couldn't save the timings: {}", error));
+    }
+
+    println!("seed: {}", Random::get_seed());
+    println!("{}", summary);
+}